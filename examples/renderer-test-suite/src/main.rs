@@ -1,7 +1,11 @@
-use conformer::{run_test_suite, TestCase, TestCaseResult, TestSuite, ViewHtml, ViewSimpleText};
+use conformer::{
+    Assertions, HtmlReport, ImageComparison, RgbaImage, TestArgs, TestCase, TestCaseResult,
+    TestOutcome, TestRunner, TestSuite, TrustedHtml, ViewSimpleText,
+};
 use renderer_test_suite::{MySimpleRendererImplementation, RenderCommand, SimpleRenderer};
 use std::any::Any;
 use std::path::PathBuf;
+use std::time::Duration;
 
 const RED: [u8; 4] = [255, 0, 0, 255];
 const BLUE: [u8; 4] = [0, 0, 255, 255];
@@ -36,7 +40,32 @@ fn main() {
         .map(|t| Box::new(t) as Box<dyn TestCase<Box<dyn SimpleRenderer>>>)
         .collect();
 
-    let test_results = run_test_suite(
+    let mut test_suite = TestSuite::new(
+        "Simple Renderer Test Suite".to_string(),
+        "Various tests to ensure that an implementation of SimpleRenderer is working properly."
+            .to_string(),
+        test_cases,
+    );
+
+    // Mirrors `cargo test [FILTER] [--exact] [--list]`, so you can run a single test case with
+    // `cargo run -p renderer-test-suite -- "All Blue Pixels" --exact`.
+    let args = TestArgs::parse(std::env::args().skip(1));
+    if let Some(filter) = &args.filter {
+        test_suite.apply_filter(filter);
+    }
+    if let Some(tag_filter) = &args.tags {
+        test_suite.apply_tag_filter(tag_filter);
+    }
+    if args.list {
+        println!("{}", ViewSimpleText::new().list_test_cases(&test_suite));
+        return;
+    }
+
+    // Print each test case's result as soon as it finishes.
+    let mut runner =
+        TestRunner::new().with_visualizer(ViewSimpleText::new().with_streaming(std::io::stdout()));
+
+    let test_results = runner.run(
         |test_case| {
             let test_case = test_case.as_any();
             let test_case = test_case
@@ -48,23 +77,13 @@ fn main() {
                 test_case.height(),
             ))
         },
-        TestSuite::new(
-            "Simple Renderer Test Suite".to_string(),
-            "Various tests to ensure that an implementation of SimpleRenderer is working properly."
-                .to_string(),
-            test_cases,
-        ),
+        test_suite,
     );
 
-    let simple_visual = ViewSimpleText::new().process_test_results(&test_results);
-    println!("{}", simple_visual);
-
-    let html_visual = ViewHtml::new().process_test_results(&test_results);
-    let out_dir = target_dir().join("html-visual");
-    let out_html_file = out_dir.join("index.html");
+    println!();
 
-    std::fs::create_dir_all(out_dir).unwrap();
-    std::fs::write(&out_html_file, html_visual).unwrap();
+    // Each test case gets its own page, and their images are written to their own files.
+    let out_html_file = HtmlReport::new(target_dir().join("html-visual")).write(&test_results);
 
     println!(
         r#"HTML test suite visual written to:
@@ -126,17 +145,21 @@ trait SimpleRendererTestCase: TestCase<Box<dyn SimpleRenderer>> {
 
 impl TestCase<Box<dyn SimpleRenderer>> for EntirePixelBufferTestCase {
     fn run(self: Box<Self>, mut simple_renderer: Box<dyn SimpleRenderer>) -> TestCaseResult {
-        simple_renderer.render(&vec![self.command]);
-
-        let actual_rgba_pixels = simple_renderer.rgba_pixels();
+        simple_renderer.render(&[self.command]);
 
-        let did_pass = &actual_rgba_pixels == &self.expected_pixels;
+        let actual = RgbaImage::new(self.width, self.height, simple_renderer.rgba_pixels());
+        let expected = RgbaImage::new(self.width, self.height, self.expected_pixels);
 
-        let html_visual = self.make_test_results_html(&actual_rgba_pixels);
+        // Tolerate the small rounding differences that different GPU drivers produce.
+        let image_diff = ImageComparison::new().with_channel_tolerance(2).compare(
+            "Pixel buffer",
+            &expected,
+            &actual,
+        );
 
         let mut test_case_result =
-            TestCaseResult::new(self.title.to_string(), "".to_string(), did_pass);
-        test_case_result.insert_metadata("html-visual".to_string(), html_visual);
+            TestCaseResult::new(self.title.to_string(), "".to_string(), TestOutcome::Passed);
+        test_case_result.push_image_diff(image_diff);
 
         test_case_result
     }
@@ -144,6 +167,10 @@ impl TestCase<Box<dyn SimpleRenderer>> for EntirePixelBufferTestCase {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn title(&self) -> &str {
+        self.title
+    }
 }
 
 impl TestCase<Box<dyn SimpleRenderer>> for FirstPixelTestCase {
@@ -153,7 +180,12 @@ impl TestCase<Box<dyn SimpleRenderer>> for FirstPixelTestCase {
         let rgba_pixels = simple_renderer.rgba_pixels();
         let actual = &rgba_pixels[0..4];
 
-        let did_pass = actual == &self.expected_pixel;
+        let mut assertions = Assertions::new();
+        let did_pass = assertions.assert_eq(
+            "The first pixel was the wrong color.",
+            &self.expected_pixel[..],
+            actual,
+        );
 
         let html_visual = if did_pass {
             let pixel_html = self.make_pixel_html(self.expected_pixel);
@@ -190,12 +222,9 @@ impl TestCase<Box<dyn SimpleRenderer>> for FirstPixelTestCase {
             )
         };
 
-        let mut test_case_result = TestCaseResult::new(
-            self.title.to_string(),
-            self.description.to_string(),
-            did_pass,
-        );
-        test_case_result.insert_metadata("html-visual".to_string(), html_visual);
+        let mut test_case_result =
+            assertions.into_test_case_result(self.title.to_string(), self.description.to_string());
+        test_case_result.set_html_visual(TrustedHtml::new(html_visual));
 
         test_case_result
     }
@@ -203,6 +232,14 @@ impl TestCase<Box<dyn SimpleRenderer>> for FirstPixelTestCase {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn title(&self) -> &str {
+        self.title
+    }
+
+    fn description(&self) -> &str {
+        self.description
+    }
 }
 
 impl TestCase<Box<dyn SimpleRenderer>> for Box<dyn SimpleRendererTestCase> {
@@ -213,6 +250,26 @@ impl TestCase<Box<dyn SimpleRenderer>> for Box<dyn SimpleRendererTestCase> {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn title(&self) -> &str {
+        (**self).title()
+    }
+
+    fn description(&self) -> &str {
+        (**self).description()
+    }
+
+    fn tags(&self) -> &[String] {
+        (**self).tags()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        (**self).timeout()
+    }
+
+    fn expect_failure(&self) -> bool {
+        (**self).expect_failure()
+    }
 }
 
 impl SimpleRendererTestCase for EntirePixelBufferTestCase {
@@ -252,56 +309,6 @@ impl FirstPixelTestCase {
     }
 }
 
-impl EntirePixelBufferTestCase {
-    fn make_test_results_html(&self, rgba_pixels: &[u8]) -> String {
-        let width = self.width;
-        let height = self.height;
-
-        let total_rgba_bytes = width * height * 4;
-
-        let mut actual_png = Vec::with_capacity(total_rgba_bytes as usize);
-        let mut expected_png = Vec::with_capacity(total_rgba_bytes as usize);
-
-        {
-            let mut actual_encoder = png::Encoder::new(&mut actual_png, width, height);
-            actual_encoder.set_color(png::ColorType::RGBA);
-            actual_encoder.set_depth(png::BitDepth::Eight);
-
-            let mut expected_encoder = png::Encoder::new(&mut expected_png, width, height);
-            expected_encoder.set_color(png::ColorType::RGBA);
-            expected_encoder.set_depth(png::BitDepth::Eight);
-
-            let mut writer = actual_encoder.write_header().unwrap();
-            writer.write_image_data(rgba_pixels).unwrap();
-
-            let mut writer = expected_encoder.write_header().unwrap();
-            writer.write_image_data(&self.expected_pixels).unwrap();
-        }
-
-        let actual = base64::encode(actual_png);
-        let expected = base64::encode(expected_png);
-
-        format!(
-            r#"
-<div style="margin-bottom: 20px;">
-  <div style="display: flex; flex-wrap: wrap;">
-    <div style="margin-right: 10px">
-      <div>Actual</div>
-      <img src="data:image/png;base64,{actual}" />
-    </div>
-    <div>
-      <div>Expected</div>
-      <img src="data:image/png;base64,{expected}" />
-    </div>
-  </div>
-</div>
-            "#,
-            actual = actual,
-            expected = expected
-        )
-    }
-}
-
 fn target_dir() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target")
 }
@@ -0,0 +1,232 @@
+use crate::{TestCaseResult, TestCheck, TestFailure, TestOutcome};
+use std::fmt::Debug;
+
+/// Accumulates the named checks that a test case makes, so that every check is reported instead
+/// of only the first one that failed.
+///
+/// Each check's message is used as the name of its [`TestCheck`], and as the message of its
+/// [`TestFailure`] when it fails.
+///
+/// ```
+/// # use conformer::{Assertions, TestOutcome};
+/// let mut assertions = Assertions::new();
+///
+/// assertions.assert_eq("The first pixel", &[0, 0, 255, 255], &[0, 0, 255, 255]);
+/// assertions.assert_approx_eq("The depth", 0.5, 0.5000001, 0.001);
+/// assertions.assert_contains("The log", "frame 1 rendered", "rendered");
+///
+/// let result = assertions.into_test_case_result("Render quads".to_string(), "".to_string());
+/// assert_eq!(result.outcome(), TestOutcome::Passed);
+/// ```
+#[derive(Debug, Default)]
+pub struct Assertions {
+    checks: Vec<TestCheck>,
+}
+
+impl Assertions {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Assertions::default()
+    }
+
+    /// Check that the condition is true.
+    ///
+    /// Returns whether or not the check passed.
+    pub fn assert(&mut self, message: &str, condition: bool) -> bool {
+        self.push(message, condition, || TestFailure::new(message.to_string()))
+    }
+
+    /// Check that the actual value equals the expected value.
+    ///
+    /// Returns whether or not the check passed. See [`TestFailure::with_expected_actual`].
+    pub fn assert_eq<T: PartialEq + Debug + ?Sized>(
+        &mut self,
+        message: &str,
+        expected: &T,
+        actual: &T,
+    ) -> bool {
+        self.push(message, expected == actual, || {
+            TestFailure::new(message.to_string()).with_expected_actual(expected, actual)
+        })
+    }
+
+    /// Check that the actual value is within the tolerance of the expected value, such as when
+    /// comparing floating point values.
+    ///
+    /// Returns whether or not the check passed.
+    pub fn assert_approx_eq(
+        &mut self,
+        message: &str,
+        expected: f64,
+        actual: f64,
+        tolerance: f64,
+    ) -> bool {
+        let difference = (expected - actual).abs();
+
+        self.push(message, difference <= tolerance, || {
+            TestFailure::new(message.to_string())
+                .with_details(format!(
+                    "The difference of {} is greater than the tolerance of {}.",
+                    difference, tolerance
+                ))
+                .with_expected_actual(&expected, &actual)
+        })
+    }
+
+    /// Check that the text contains the expected text.
+    ///
+    /// Returns whether or not the check passed.
+    pub fn assert_contains(&mut self, message: &str, text: &str, expected: &str) -> bool {
+        self.push(message, text.contains(expected), || {
+            TestFailure::new(message.to_string())
+                .with_details(format!("{:?} does not contain {:?}.", text, expected))
+        })
+    }
+
+    /// Record a check that was made some other way, such as by comparing two images.
+    pub fn push_check(&mut self, check: TestCheck) {
+        self.checks.push(check);
+    }
+
+    /// The checks that have been made so far.
+    pub fn checks(&self) -> &[TestCheck] {
+        &self.checks
+    }
+
+    /// Whether or not all of the checks so far have passed.
+    pub fn did_pass(&self) -> bool {
+        self.checks.iter().all(|check| check.did_pass())
+    }
+
+    /// Create a result with every check, which passed if all of the checks passed and otherwise
+    /// failed. See [`TestCaseResult::checks`].
+    pub fn into_test_case_result(self, title: String, description: String) -> TestCaseResult {
+        let mut result = TestCaseResult::new(title, description, TestOutcome::Passed);
+        for check in self.checks {
+            result.push_check(check);
+        }
+
+        result
+    }
+
+    fn push(
+        &mut self,
+        message: &str,
+        did_pass: bool,
+        failure: impl FnOnce() -> TestFailure,
+    ) -> bool {
+        let check = if did_pass {
+            TestCheck::passed(message.to_string())
+        } else {
+            TestCheck::failed(message.to_string(), failure())
+        };
+        self.checks.push(check);
+
+        did_pass
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DiffLine;
+
+    /// Verify that every check that failed is reported.
+    #[test]
+    fn every_failed_check_is_reported() {
+        let mut assertions = Assertions::new();
+
+        assert!(!assertions.assert_eq("The first pixel", &[0, 0, 255], &[255, 0, 0]));
+        assert!(assertions.assert_eq("The second pixel", &[0, 0, 255], &[0, 0, 255]));
+        assert!(!assertions.assert_approx_eq("The depth", 0.5, 0.6, 0.01));
+        assert!(!assertions.assert_contains("The log", "frame 1 dropped", "rendered"));
+        assert!(!assertions.assert("The renderer is idle", false));
+
+        let result = assertions.into_test_case_result("Render quads".to_string(), "".to_string());
+        assert_eq!(result.outcome(), TestOutcome::Failed);
+
+        let failures: Vec<&str> = result.all_failures().map(|f| f.message()).collect();
+        assert_eq!(
+            failures,
+            vec![
+                "The first pixel",
+                "The depth",
+                "The log",
+                "The renderer is idle"
+            ]
+        );
+
+        let checks: Vec<(&str, bool)> = result
+            .checks()
+            .iter()
+            .map(|check| (check.name(), check.did_pass()))
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                ("The first pixel", false),
+                ("The second pixel", true),
+                ("The depth", false),
+                ("The log", false),
+                ("The renderer is idle", false)
+            ]
+        );
+
+        let first = result.checks()[0].failure().unwrap();
+        assert_eq!(first.expected(), Some("[0, 0, 255]"));
+        assert_eq!(first.actual(), Some("[255, 0, 0]"));
+    }
+
+    /// Verify that long values are pretty printed so that they can be shown as a diff.
+    #[test]
+    fn long_values_are_diffed() {
+        #[derive(Debug, PartialEq)]
+        struct Vertex {
+            position: [f32; 3],
+            normal: [f32; 3],
+            uv: [f32; 2],
+            color: [f32; 4],
+        }
+        let expected = Vertex {
+            position: [1.0, 2.0, 3.0],
+            normal: [0.0, 1.0, 0.0],
+            uv: [0.5, 0.5],
+            color: [1.0, 1.0, 1.0, 1.0],
+        };
+        let actual = Vertex {
+            position: [1.0, 2.0, 3.0],
+            normal: [0.0, 0.0, 1.0],
+            uv: [0.5, 0.5],
+            color: [1.0, 1.0, 1.0, 1.0],
+        };
+
+        let mut assertions = Assertions::new();
+        assertions.assert_eq("The vertex", &expected, &actual);
+
+        let diff = assertions.checks()[0].failure().unwrap().diff().unwrap();
+        assert_eq!(diff[0], DiffLine::Unchanged(5));
+        assert!(diff
+            .iter()
+            .any(|line| matches!(line, DiffLine::Expected(_))));
+        assert!(diff.iter().any(|line| matches!(line, DiffLine::Actual(_))));
+    }
+
+    /// Verify that a large pixel buffer with a few wrong pixels is diffed quickly and only shows
+    /// the lines around the wrong pixels.
+    #[test]
+    fn large_buffers_are_diffed() {
+        let expected = vec![255u8; 256 * 256 * 4];
+        let mut actual = expected.clone();
+        actual[1000] = 0;
+        actual[200_000] = 0;
+
+        let mut assertions = Assertions::new();
+        assertions.assert_eq("The pixels", &expected, &actual);
+
+        let failure = assertions.checks()[0].failure().unwrap();
+        let diff = failure.diff().unwrap();
+        assert_eq!(diff.len(), 19);
+        assert_eq!(diff[0], DiffLine::Unchanged(998));
+        assert!(failure.to_string().lines().count() < 30);
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{ImageDiff, ImageDiffStats};
+
+pub use self::rgba_image::*;
+
+mod rgba_image;
+
+/// The color that differing pixels are drawn in the diff image.
+const DIFF_COLOR: [u8; 4] = [255, 0, 0, 255];
+
+/// How strongly matching pixels are faded towards white in the diff image, so that the differing
+/// pixels stand out while the shape of the image is still visible.
+const DIFF_FADE: f64 = 0.9;
+
+/// The squared YIQ distance between black and white, used to normalize the perceptual delta so
+/// that black and white are 1.0 apart.
+const BLACK_WHITE_YIQ_DELTA: f64 = 0.5053 * 255.0 * 255.0;
+
+/// Compares images while tolerating small differences, such as those between GPU drivers.
+///
+/// By default every channel of every pixel must match exactly.
+///
+/// ```
+/// # use conformer::{ImageComparison, RgbaImage};
+/// let expected = RgbaImage::new(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+/// let actual = RgbaImage::new(2, 1, vec![253, 0, 0, 255, 0, 0, 255, 255]);
+///
+/// let comparison = ImageComparison::new().with_channel_tolerance(2);
+/// let image_diff = comparison.compare("Framebuffer", &expected, &actual);
+///
+/// assert!(image_diff.did_pass());
+/// assert_eq!(image_diff.stats().max_channel_delta, 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ImageComparison {
+    channel_tolerance: u8,
+    perceptual_threshold: Option<f64>,
+    max_differing_pixels: usize,
+}
+
+impl ImageComparison {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        ImageComparison::default()
+    }
+
+    /// How much each channel of a pixel is allowed to differ, from 0 to 255, before the pixel is
+    /// considered to differ.
+    pub fn with_channel_tolerance(mut self, channel_tolerance: u8) -> Self {
+        self.channel_tolerance = channel_tolerance;
+        self
+    }
+
+    /// Compare pixels by how different their colors look instead of channel by channel, where
+    /// 0.0 means identical and 1.0 means as different as black and white.
+    ///
+    /// Colors are compared in the YIQ color space, which weighs differences in brightness more
+    /// heavily than differences in hue. Overrides the channel tolerance.
+    pub fn with_perceptual_threshold(mut self, perceptual_threshold: f64) -> Self {
+        self.perceptual_threshold = Some(perceptual_threshold);
+        self
+    }
+
+    /// How many pixels are allowed to differ before the images are considered to not match.
+    pub fn with_max_differing_pixels(mut self, max_differing_pixels: usize) -> Self {
+        self.max_differing_pixels = max_differing_pixels;
+        self
+    }
+
+    /// Compare the actual image to the expected image, producing an image that highlights the
+    /// pixels that differ in red.
+    ///
+    /// Images of different sizes never match. Pixels that are only in one of the images are
+    /// considered to differ.
+    pub fn compare(&self, name: &str, expected: &RgbaImage, actual: &RgbaImage) -> ImageDiff {
+        let width = expected.width().max(actual.width());
+        let height = expected.height().max(actual.height());
+
+        let mut diff_pixels = Vec::with_capacity(width as usize * height as usize * 4);
+        let stats = self.compare_pixels(expected, actual, |diff_pixel| {
+            diff_pixels.extend_from_slice(&diff_pixel)
+        });
+
+        ImageDiff::new(
+            name.to_string(),
+            stats,
+            png(expected),
+            png(actual),
+            png(&RgbaImage::new(width, height, diff_pixels)),
+        )
+    }
+
+    /// Compare the actual image to the expected image like [`ImageComparison::compare`], but only
+    /// compute the statistics, without encoding any images.
+    pub fn stats(&self, expected: &RgbaImage, actual: &RgbaImage) -> ImageDiffStats {
+        self.compare_pixels(expected, actual, |_| {})
+    }
+
+    /// Compare the images pixel by pixel, passing each pixel of the diff image to `diff_pixel`
+    /// row by row.
+    fn compare_pixels(
+        &self,
+        expected: &RgbaImage,
+        actual: &RgbaImage,
+        mut diff_pixel: impl FnMut([u8; 4]),
+    ) -> ImageDiffStats {
+        let width = expected.width().max(actual.width());
+        let height = expected.height().max(actual.height());
+
+        let mut differing_pixel_count = 0;
+        let mut max_channel_delta = 0;
+        let mut max_perceptual_delta: f64 = 0.0;
+
+        for y in 0..height {
+            for x in 0..width {
+                let (expected, actual) = match (expected.pixel(x, y), actual.pixel(x, y)) {
+                    (Some(expected), Some(actual)) => (expected, actual),
+                    _ => {
+                        differing_pixel_count += 1;
+                        diff_pixel(DIFF_COLOR);
+                        continue;
+                    }
+                };
+
+                let channel_delta = expected
+                    .iter()
+                    .zip(actual.iter())
+                    .map(|(e, a)| (*e as i16 - *a as i16).unsigned_abs() as u8)
+                    .max()
+                    .unwrap();
+                let perceptual_delta = perceptual_delta(expected, actual);
+                max_channel_delta = max_channel_delta.max(channel_delta);
+                max_perceptual_delta = max_perceptual_delta.max(perceptual_delta);
+
+                let differs = match self.perceptual_threshold {
+                    Some(threshold) => perceptual_delta > threshold,
+                    None => channel_delta > self.channel_tolerance,
+                };
+
+                if differs {
+                    differing_pixel_count += 1;
+                    diff_pixel(DIFF_COLOR);
+                } else {
+                    diff_pixel(faded(expected));
+                }
+            }
+        }
+
+        let same_size = (expected.width(), expected.height()) == (actual.width(), actual.height());
+        ImageDiffStats {
+            did_pass: same_size && differing_pixel_count <= self.max_differing_pixels,
+            pixel_count: width as usize * height as usize,
+            differing_pixel_count,
+            max_channel_delta,
+            max_perceptual_delta,
+        }
+    }
+}
+
+/// The image as a PNG, or no bytes if the image has no pixels and so cannot be encoded.
+fn png(image: &RgbaImage) -> Vec<u8> {
+    image.to_png().unwrap_or_default()
+}
+
+/// The perceptual difference between two colors from 0.0 to 1.0, after blending them onto a
+/// white background.
+fn perceptual_delta(expected: [u8; 4], actual: [u8; 4]) -> f64 {
+    let (y1, i1, q1) = yiq(expected);
+    let (y2, i2, q2) = yiq(actual);
+
+    let delta = 0.5053 * (y1 - y2).powi(2) + 0.299 * (i1 - i2).powi(2) + 0.1957 * (q1 - q2).powi(2);
+
+    (delta / BLACK_WHITE_YIQ_DELTA).sqrt().min(1.0)
+}
+
+fn yiq(rgba: [u8; 4]) -> (f64, f64, f64) {
+    let alpha = rgba[3] as f64 / 255.0;
+    let blend = |channel: u8| 255.0 + (channel as f64 - 255.0) * alpha;
+    let (r, g, b) = (blend(rgba[0]), blend(rgba[1]), blend(rgba[2]));
+
+    (
+        r * 0.29889531 + g * 0.58662247 + b * 0.11448223,
+        r * 0.59597799 - g * 0.2741761 - b * 0.32180189,
+        r * 0.21147017 - g * 0.52261711 + b * 0.31114694,
+    )
+}
+
+/// The pixel's brightness, faded towards white.
+fn faded(rgba: [u8; 4]) -> [u8; 4] {
+    let (brightness, _, _) = yiq(rgba);
+    let faded = (255.0 + (brightness - 255.0) * (1.0 - DIFF_FADE)).round() as u8;
+
+    [faded, faded, faded, 255]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    /// Verify that pixels within the channel tolerance do not differ.
+    #[test]
+    fn channel_tolerance() {
+        let expected = RgbaImage::new(2, 1, [RED, BLUE].concat());
+        let actual = RgbaImage::new(2, 1, [[250, 0, 0, 255], BLUE].concat());
+
+        let exact = ImageComparison::new().compare("Framebuffer", &expected, &actual);
+        assert!(!exact.did_pass());
+        assert_eq!(exact.stats().differing_pixel_count, 1);
+        assert_eq!(exact.stats().max_channel_delta, 5);
+
+        let tolerant = ImageComparison::new().with_channel_tolerance(5).compare(
+            "Framebuffer",
+            &expected,
+            &actual,
+        );
+        assert!(tolerant.did_pass());
+        assert_eq!(tolerant.stats().differing_pixel_count, 0);
+
+        assert_eq!(
+            ImageComparison::new().stats(&expected, &actual),
+            exact.stats()
+        );
+    }
+
+    /// Verify that a number of differing pixels can be allowed.
+    #[test]
+    fn max_differing_pixels() {
+        let expected = RgbaImage::new(2, 1, [RED, RED].concat());
+        let actual = RgbaImage::new(2, 1, [RED, BLUE].concat());
+
+        let image_diff = ImageComparison::new().with_max_differing_pixels(1).compare(
+            "Framebuffer",
+            &expected,
+            &actual,
+        );
+
+        assert!(image_diff.did_pass());
+        assert_eq!(image_diff.stats().differing_pixel_count, 1);
+    }
+
+    /// Verify that the perceptual delta ranges from identical to black versus white.
+    #[test]
+    fn perceptual_threshold() {
+        assert_eq!(perceptual_delta(RED, RED), 0.0);
+        assert!((perceptual_delta([0, 0, 0, 255], [255, 255, 255, 255]) - 1.0).abs() < 0.01);
+
+        let expected = RgbaImage::new(1, 1, RED.to_vec());
+        let actual = RgbaImage::new(1, 1, vec![250, 5, 0, 255]);
+
+        let image_diff = ImageComparison::new()
+            .with_perceptual_threshold(0.05)
+            .compare("Framebuffer", &expected, &actual);
+        assert!(image_diff.did_pass());
+    }
+
+    /// Verify that images of different sizes never match.
+    #[test]
+    fn different_sizes() {
+        let expected = RgbaImage::new(1, 1, RED.to_vec());
+        let actual = RgbaImage::new(2, 1, [RED, RED].concat());
+
+        let image_diff = ImageComparison::new()
+            .with_max_differing_pixels(10)
+            .compare("Framebuffer", &expected, &actual);
+
+        assert!(!image_diff.did_pass());
+        assert_eq!(image_diff.stats().pixel_count, 2);
+        assert_eq!(image_diff.stats().differing_pixel_count, 1);
+        assert!(image_diff.diff_png().starts_with(b"\x89PNG"));
+    }
+
+    /// Verify that images without any pixels match without being encoded as PNGs.
+    #[test]
+    fn empty_images() {
+        let empty = RgbaImage::new(0, 0, vec![]);
+
+        let image_diff = ImageComparison::new().compare("Framebuffer", &empty, &empty);
+
+        assert!(image_diff.did_pass());
+        assert_eq!(image_diff.stats().pixel_count, 0);
+        assert!(image_diff.expected_png().is_empty());
+        assert!(image_diff.diff_png().is_empty());
+        assert!(empty.to_png().is_err());
+    }
+}
@@ -0,0 +1,94 @@
+/// An image made up of 8-bit RGBA pixels, row by row starting at the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// # Panics
+    ///
+    /// Panics if the number of bytes does not match the width and height.
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width as usize * height as usize * 4,
+            "A {}x{} RGBA image must have 4 bytes per pixel.",
+            width,
+            height
+        );
+
+        RgbaImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// The image's width, in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The image's height, in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The image's RGBA bytes.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// The pixel at the given position, or `None` if it is outside of the image.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let idx = (y as usize * self.width as usize + x as usize) * 4;
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.pixels[idx..idx + 4]);
+        Some(pixel)
+    }
+
+    /// Decode an 8-bit RGBA PNG, such as one written by [`RgbaImage::to_png`].
+    pub fn from_png(png_bytes: &[u8]) -> Result<Self, String> {
+        let (info, mut reader) = png::Decoder::new(png_bytes)
+            .read_info()
+            .map_err(|error| error.to_string())?;
+        if info.color_type != png::ColorType::RGBA || info.bit_depth != png::BitDepth::Eight {
+            return Err(format!(
+                "expected an 8-bit RGBA PNG but it was {:?} {:?}",
+                info.bit_depth, info.color_type
+            ));
+        }
+
+        let mut pixels = vec![0; info.buffer_size()];
+        reader
+            .next_frame(&mut pixels)
+            .map_err(|error| error.to_string())?;
+
+        Ok(RgbaImage::new(info.width, info.height, pixels))
+    }
+
+    /// Encode the image as a PNG.
+    ///
+    /// Fails if the image has no pixels, since a PNG must be at least 1x1.
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        let mut png_bytes = vec![];
+
+        {
+            let mut encoder = png::Encoder::new(&mut png_bytes, self.width, self.height);
+            encoder.set_color(png::ColorType::RGBA);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder
+                .write_header()
+                .and_then(|mut writer| writer.write_image_data(&self.pixels))
+                .map_err(|error| error.to_string())?;
+        }
+
+        Ok(png_bytes)
+    }
+}
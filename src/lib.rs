@@ -2,32 +2,51 @@
 
 #![deny(missing_docs)]
 
+pub use self::assertions::*;
+#[cfg(feature = "images")]
+pub use self::image_comparison::*;
+#[cfg(feature = "snapshots")]
+pub use self::snapshots::*;
+pub use self::tag_filter::*;
+pub use self::test_args::*;
 pub use self::test_case_results::*;
+pub use self::test_filter::*;
+pub use self::test_matrix::*;
+pub use self::test_runner::*;
 pub use self::test_suite::*;
 pub use self::visualizer::*;
 
+mod assertions;
+#[cfg(feature = "images")]
+mod image_comparison;
+#[cfg(feature = "snapshots")]
+mod snapshots;
+mod tag_filter;
+mod test_args;
 mod test_case_results;
+mod test_filter;
+mod test_matrix;
+mod test_runner;
 mod test_suite;
 mod visualizer;
 
-/// Run a test suite.
-pub fn run_test_suite<TypeToTest, TypeToTestCreator>(
+/// Run a test suite, one test case at a time.
+///
+/// See [`TestRunner`] for running test cases in parallel.
+///
+/// The test cases run on the current thread, so timeouts cannot be enforced: a test case that
+/// exceeds its timeout is only recorded as timed out after it finishes, one that never finishes
+/// hangs the suite, and a warning is printed to stderr. Use [`TestRunner::run_with_timeouts`] to
+/// give up on such test cases.
+pub fn run_test_suite<TypeToTest, Case, TypeToTestCreator>(
     type_to_test_creator: TypeToTestCreator,
-    suite: TestSuite<TypeToTest>,
+    suite: TestSuite<TypeToTest, Case>,
 ) -> TestCaseResults
 where
-    TypeToTestCreator: Fn(&Box<dyn TestCase<TypeToTest>>) -> TypeToTest,
+    Case: ?Sized + TestCase<TypeToTest>,
+    TypeToTestCreator: Fn(&Box<Case>) -> TypeToTest,
 {
-    let mut results = Vec::with_capacity(suite.test_cases.len());
-
-    for test_case in suite.test_cases {
-        let type_to_test = type_to_test_creator(&test_case);
-
-        let test_case_result = test_case.run(type_to_test);
-        results.push(test_case_result);
-    }
-
-    TestCaseResults::new(suite.title, suite.description, results)
+    TestRunner::new().run(type_to_test_creator, suite)
 }
 
 /// Used to indicate a type that can be used to create test cases to test some aspect of a
@@ -47,4 +66,38 @@ pub trait TestCase<TypeToTest> {
     /// # }
     /// ```
     fn as_any(&self) -> &dyn std::any::Any;
+
+    /// The test case's title, used to identify the test case before it is run.
+    ///
+    /// Defaults to the name of the test case's type.
+    fn title(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// The test case's description. Defaults to an empty string.
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// Tags that describe what the test case is testing, such as "textures" or "skinning".
+    /// Defaults to no tags.
+    fn tags(&self) -> &[String] {
+        &[]
+    }
+
+    /// How long the test case is allowed to run before it is considered to have timed out.
+    ///
+    /// Overrides the [`TestSuite::timeout`]. Defaults to `None`, meaning that the suite's timeout
+    /// is used.
+    fn timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// Whether or not the test case is known to be broken.
+    ///
+    /// The outcome of a test case that is expected to fail is converted using
+    /// [`TestOutcome::expecting_failure`], so that it does not fail the test suite.
+    fn expect_failure(&self) -> bool {
+        false
+    }
 }
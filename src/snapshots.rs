@@ -0,0 +1,268 @@
+use crate::{ImageComparison, RgbaImage, TestCheck, TestFailure};
+use std::path::{Path, PathBuf};
+
+/// The environment variable that [`Snapshots::new`] checks to see if golden images should be
+/// written instead of compared against. Any value other than an empty one or `0` enables blessing.
+pub const BLESS_ENV_VAR: &str = "CONFORMER_BLESS";
+
+/// Golden images stored as PNGs in a directory, one per test case, that the pixels a test case
+/// produced are compared against.
+///
+/// When blessing, the pixels are written as the new golden image instead of being compared, which
+/// is how golden images are first created and later updated after an intentional change.
+///
+/// ```no_run
+/// # use conformer::{Assertions, ImageComparison, Snapshots};
+/// let snapshots =
+///     Snapshots::new("tests/golden").with_comparison(ImageComparison::new().with_channel_tolerance(2));
+/// let rgba_pixels = vec![255; 2 * 2 * 4];
+///
+/// let mut assertions = Assertions::new();
+/// assertions.push_check(snapshots.check_rgba("All red pixels", 2, 2, &rgba_pixels));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Snapshots {
+    directory: PathBuf,
+    bless: bool,
+    comparison: ImageComparison,
+}
+
+impl Snapshots {
+    /// Use the golden images in the directory, blessing them if the [`BLESS_ENV_VAR`] environment
+    /// variable is enabled.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        let bless = std::env::var(BLESS_ENV_VAR)
+            .map(|value| !value.is_empty() && value != "0")
+            .unwrap_or(false);
+
+        Snapshots {
+            directory: directory.into(),
+            bless,
+            comparison: ImageComparison::new(),
+        }
+    }
+
+    /// Set whether the golden images should be written instead of compared against, overriding
+    /// the [`BLESS_ENV_VAR`] environment variable.
+    pub fn with_bless(mut self, bless: bool) -> Self {
+        self.bless = bless;
+        self
+    }
+
+    /// Compare pixels against the golden images using the comparison, such as one that tolerates
+    /// small differences between GPU drivers. By default every pixel must match exactly.
+    pub fn with_comparison(mut self, comparison: ImageComparison) -> Self {
+        self.comparison = comparison;
+        self
+    }
+
+    /// The directory that the golden images are stored in.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Whether the golden images are written instead of compared against.
+    pub fn bless(&self) -> bool {
+        self.bless
+    }
+
+    /// Where the test case's golden image is stored.
+    ///
+    /// Characters in the title that are not ASCII letters, digits, `-` or `_` are replaced with
+    /// `_`, and a hash of the title is appended so that titles such as `Render quads (MSAA)` and
+    /// `Render quads [MSAA]` are stored in different files.
+    pub fn path(&self, title: &str) -> PathBuf {
+        let file_name: String = title
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        self.directory
+            .join(format!("{}-{:08x}.png", file_name, fnv1a(title.as_bytes())))
+    }
+
+    /// Read the test case's golden image, such as to compare it against with an
+    /// [`ImageComparison`] and show the resulting [`crate::ImageDiff`] in reports.
+    pub fn load(&self, title: &str) -> Result<RgbaImage, String> {
+        let path = self.path(title);
+
+        std::fs::read(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|png_bytes| RgbaImage::from_png(&png_bytes))
+            .map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    /// Compare the 8-bit RGBA pixels against the test case's golden image, or write them as the
+    /// new golden image when blessing.
+    ///
+    /// The check fails if the golden image is missing, could not be read, has different
+    /// dimensions or has pixels that differ by more than the comparison allows.
+    ///
+    /// Only the statistics of the comparison are computed. To show the golden image, the actual
+    /// image and their differences in reports, [`Snapshots::load`] the golden image and push the
+    /// [`ImageComparison::compare`] of it onto the test case's result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of bytes does not match the width and height.
+    pub fn check_rgba(&self, title: &str, width: u32, height: u32, rgba: &[u8]) -> TestCheck {
+        let actual = RgbaImage::new(width, height, rgba.to_vec());
+
+        let path = self.path(title);
+        let check_name = format!("Matches the golden image {}", path.display());
+
+        if self.bless {
+            return match write_golden(&path, &actual) {
+                Ok(()) => TestCheck::passed(check_name),
+                Err(error) => TestCheck::failed(
+                    check_name,
+                    TestFailure::new(format!("Could not write the golden image: {}", error)),
+                ),
+            };
+        }
+
+        let golden = match self.load(title) {
+            Ok(golden) => golden,
+            Err(error) => {
+                return TestCheck::failed(
+                    check_name,
+                    TestFailure::new(format!("Could not read the golden image: {}", error))
+                        .with_details(format!(
+                            "Run with {}=1 to create it from the actual pixels.",
+                            BLESS_ENV_VAR
+                        )),
+                )
+            }
+        };
+
+        if (golden.width(), golden.height()) != (width, height) {
+            return TestCheck::failed(
+                check_name,
+                TestFailure::new("The image was not the same size as the golden image.".into())
+                    .with_expected_actual_text(
+                        format!("{}x{}", golden.width(), golden.height()),
+                        format!("{}x{}", width, height),
+                    ),
+            );
+        }
+
+        let stats = self.comparison.stats(&golden, &actual);
+        if !stats.did_pass {
+            return TestCheck::failed(
+                check_name,
+                TestFailure::new(format!(
+                    "{} of the {} pixels did not match the golden image.",
+                    stats.differing_pixel_count, stats.pixel_count
+                ))
+                .with_details(stats.summary()),
+            );
+        }
+
+        TestCheck::passed(check_name)
+    }
+}
+
+/// Write the image as a PNG, creating its directory if needed.
+fn write_golden(path: &Path, image: &RgbaImage) -> Result<(), String> {
+    let write = || -> Result<(), String> {
+        let png_bytes = image.to_png()?;
+
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory).map_err(|error| error.to_string())?;
+        }
+
+        std::fs::write(path, png_bytes).map_err(|error| error.to_string())
+    };
+
+    write().map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+/// The 32-bit FNV-1a hash, which unlike the standard library's hashers is the same across
+/// platforms and Rust versions, so golden images keep their paths.
+fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ *byte as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    /// Verify that blessed pixels are compared against on the next run.
+    #[test]
+    fn bless_then_compare() {
+        let directory = temp_directory("bless_then_compare");
+        let snapshots = Snapshots::new(&directory).with_bless(false);
+
+        let missing = snapshots.check_rgba("All red", 2, 1, &RED.repeat(2));
+        assert!(!missing.did_pass());
+
+        let blessed =
+            snapshots
+                .clone()
+                .with_bless(true)
+                .check_rgba("All red", 2, 1, &RED.repeat(2));
+        assert!(blessed.did_pass());
+        assert!(snapshots.path("All red").exists());
+        assert_eq!(
+            snapshots.load("All red").unwrap().pixels(),
+            &RED.repeat(2)[..]
+        );
+
+        assert!(snapshots
+            .check_rgba("All red", 2, 1, &RED.repeat(2))
+            .did_pass());
+
+        let mismatched = snapshots.check_rgba("All red", 2, 1, &[RED, BLUE].concat());
+        assert_eq!(
+            mismatched.failure().unwrap().message(),
+            "1 of the 2 pixels did not match the golden image."
+        );
+
+        let tolerant = snapshots
+            .clone()
+            .with_comparison(ImageComparison::new().with_max_differing_pixels(1))
+            .check_rgba("All red", 2, 1, &[RED, BLUE].concat());
+        assert!(tolerant.did_pass());
+
+        let resized = snapshots.check_rgba("All red", 1, 2, &RED.repeat(2));
+        assert_eq!(resized.failure().unwrap().expected(), Some("2x1"));
+        assert_eq!(resized.failure().unwrap().actual(), Some("1x2"));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    /// Verify that titles are turned into file names that are safe on every platform, and that
+    /// titles which sanitize to the same name are still stored in different files.
+    #[test]
+    fn path() {
+        let snapshots = Snapshots::new("golden");
+
+        let path = snapshots.path("Render quads (MSAA)");
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+        assert!(file_name.starts_with("Render_quads__MSAA_-"));
+        assert!(file_name.ends_with(".png"));
+        assert_eq!(path, snapshots.path("Render quads (MSAA)"));
+
+        assert_ne!(path, snapshots.path("Render quads [MSAA]"));
+        assert_ne!(snapshots.path("a/b"), snapshots.path("a_b"));
+    }
+
+    fn temp_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir()
+            .join("conformer-snapshots")
+            .join(format!("{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+}
@@ -0,0 +1,71 @@
+/// Selects test cases by their [`crate::TestCase::tags`].
+///
+/// ```
+/// # use conformer::TagFilter;
+/// let filter = TagFilter::parse("textures, ui, !particles");
+///
+/// assert!(filter.matches(&["ui".to_string()]));
+/// assert!(!filter.matches(&["ui".to_string(), "particles".to_string()]));
+/// assert!(!filter.matches(&["skinning".to_string()]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagFilter {
+    /// Test cases that have at least one of these tags are selected.
+    /// When empty, every test case that isn't excluded is selected.
+    pub include: Vec<String>,
+    /// Test cases that have any of these tags are not selected, even if they have an included
+    /// tag.
+    pub exclude: Vec<String>,
+}
+
+impl TagFilter {
+    /// Parse a comma separated tag expression such as `textures,ui,!particles`.
+    ///
+    /// Tags that are prefixed with `!` are excluded, all other tags are included.
+    pub fn parse(expression: &str) -> Self {
+        let mut tag_filter = TagFilter::default();
+
+        for tag in expression.split(',').map(str::trim) {
+            if let Some(excluded) = tag.strip_prefix('!') {
+                tag_filter.exclude.push(excluded.trim().to_string());
+            } else if !tag.is_empty() {
+                tag_filter.include.push(tag.to_string());
+            }
+        }
+
+        tag_filter
+    }
+
+    /// Whether or not a test case with the given tags is selected by the filter.
+    pub fn matches(&self, tags: &[String]) -> bool {
+        let is_included =
+            self.include.is_empty() || self.include.iter().any(|tag| tags.contains(tag));
+        let is_excluded = self.exclude.iter().any(|tag| tags.contains(tag));
+
+        is_included && !is_excluded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that included and excluded tags are parsed.
+    #[test]
+    fn parse() {
+        let filter = TagFilter::parse("textures,!particles, ui,");
+
+        assert_eq!(filter.include, vec!["textures", "ui"]);
+        assert_eq!(filter.exclude, vec!["particles"]);
+    }
+
+    /// Verify that a filter with only excluded tags selects every other test case.
+    #[test]
+    fn only_excluded() {
+        let filter = TagFilter::parse("!particles");
+
+        assert!(filter.matches(&[]));
+        assert!(filter.matches(&["ui".to_string()]));
+        assert!(!filter.matches(&["particles".to_string()]));
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{TagFilter, TestFilter};
+
+/// The environment variable that [`TestArgs::from_env`] reads the filter from.
+pub const FILTER_ENV_VAR: &str = "CONFORMER_FILTER";
+
+/// The environment variable that [`TestArgs::from_env`] checks to see if the filter should be an
+/// exact match. Any value other than `0` enables exact matching.
+pub const EXACT_ENV_VAR: &str = "CONFORMER_EXACT";
+
+/// The environment variable that [`TestArgs::from_env`] checks to see if the filter should be a
+/// regular expression. Any value other than `0` enables regular expressions.
+pub const REGEX_ENV_VAR: &str = "CONFORMER_REGEX";
+
+/// The environment variable that [`TestArgs::from_env`] checks to see if the test cases should be
+/// listed instead of run. Any value other than `0` enables listing.
+pub const LIST_ENV_VAR: &str = "CONFORMER_LIST";
+
+/// The environment variable that [`TestArgs::from_env`] reads the tag expression from.
+///
+/// See [`TagFilter::parse`].
+pub const TAGS_ENV_VAR: &str = "CONFORMER_TAGS";
+
+/// The flags of the Rust test harness that take a value, which is skipped instead of being
+/// mistaken for the filter.
+const LIBTEST_FLAGS_WITH_VALUES: [&str; 7] = [
+    "--test-threads",
+    "--skip",
+    "--logfile",
+    "--format",
+    "--color",
+    "--shuffle-seed",
+    "-Z",
+];
+
+/// Options for selecting which test cases to run, mirroring
+/// `cargo test [FILTER] [--exact] [--list]`, along with `[--regex]` and `[--tags EXPRESSION]`.
+///
+/// With `--regex` the filter is a [`TestFilter::Regex`], which requires the `regex` feature and
+/// takes precedence over `--exact`.
+///
+/// ```
+/// # use conformer::{TestArgs, TestFilter};
+/// let args = TestArgs::parse(vec!["quads".to_string(), "--exact".to_string()]);
+///
+/// assert!(matches!(args.filter, Some(TestFilter::Exact(filter)) if filter == "quads"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TestArgs {
+    /// Only run the test cases that match this filter.
+    pub filter: Option<TestFilter>,
+    /// Only run the test cases whose tags match this filter.
+    pub tags: Option<TagFilter>,
+    /// List the test cases instead of running them.
+    ///
+    /// See [`crate::ViewSimpleText::list_test_cases`].
+    pub list: bool,
+}
+
+impl TestArgs {
+    /// Parse command line arguments of the form
+    /// `[FILTER] [--exact] [--regex] [--list] [--tags EXPRESSION]`.
+    ///
+    /// The arguments should not include the binary name, so you'll typically want to pass in
+    /// `std::env::args().skip(1)`. Unrecognized flags are ignored, along with the values of the
+    /// Rust test harness's flags that take one, such as `--test-threads 4`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `--regex` is passed with a filter that is not a valid regular expression, or
+    /// without the `regex` feature enabled.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Self {
+        let mut filter = None;
+        let mut exact = false;
+        let mut regex = false;
+        let mut list = false;
+        let mut tags = None;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--exact" {
+                exact = true;
+            } else if arg == "--regex" {
+                regex = true;
+            } else if arg == "--list" {
+                list = true;
+            } else if arg == "--tags" {
+                tags = args.next().map(|expression| TagFilter::parse(&expression));
+            } else if let Some(expression) = arg.strip_prefix("--tags=") {
+                tags = Some(TagFilter::parse(expression));
+            } else if LIBTEST_FLAGS_WITH_VALUES.contains(&arg.as_str()) {
+                args.next();
+            } else if !arg.starts_with('-') && filter.is_none() {
+                filter = Some(arg);
+            }
+        }
+
+        TestArgs {
+            filter: filter.map(|filter| make_filter(filter, exact, regex)),
+            tags,
+            list,
+        }
+    }
+
+    /// Read the options from the [`FILTER_ENV_VAR`], [`EXACT_ENV_VAR`], [`REGEX_ENV_VAR`],
+    /// [`TAGS_ENV_VAR`] and [`LIST_ENV_VAR`] environment variables.
+    ///
+    /// This is useful when the test suite is run from within a `#[test]` function, where the
+    /// command line arguments belong to the Rust test harness.
+    ///
+    /// ```sh
+    /// CONFORMER_FILTER="textured quad" cargo test
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`TestArgs::parse`] when [`REGEX_ENV_VAR`] is enabled.
+    pub fn from_env() -> Self {
+        let exact = is_env_var_enabled(EXACT_ENV_VAR);
+        let regex = is_env_var_enabled(REGEX_ENV_VAR);
+
+        TestArgs {
+            filter: std::env::var(FILTER_ENV_VAR)
+                .ok()
+                .map(|filter| make_filter(filter, exact, regex)),
+            tags: std::env::var(TAGS_ENV_VAR)
+                .ok()
+                .map(|expression| TagFilter::parse(&expression)),
+            list: is_env_var_enabled(LIST_ENV_VAR),
+        }
+    }
+}
+
+fn is_env_var_enabled(name: &str) -> bool {
+    std::env::var(name)
+        .map(|value| value != "0")
+        .unwrap_or(false)
+}
+
+fn make_filter(filter: String, exact: bool, regex: bool) -> TestFilter {
+    if regex {
+        make_regex_filter(filter)
+    } else if exact {
+        TestFilter::Exact(filter)
+    } else {
+        TestFilter::Substring(filter)
+    }
+}
+
+#[cfg(feature = "regex")]
+fn make_regex_filter(filter: String) -> TestFilter {
+    match regex::Regex::new(&filter) {
+        Ok(regex) => TestFilter::Regex(regex),
+        Err(error) => panic!("The filter {:?} is not a valid regex: {}", filter, error),
+    }
+}
+
+#[cfg(not(feature = "regex"))]
+fn make_regex_filter(filter: String) -> TestFilter {
+    panic!(
+        "The filter {:?} cannot be used as a regex without the regex feature enabled.",
+        filter
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the first positional argument is used as a substring filter.
+    #[test]
+    fn parse_substring_filter() {
+        let args = TestArgs::parse(vec!["--nocapture".to_string(), "quads".to_string()]);
+
+        assert!(matches!(args.filter, Some(TestFilter::Substring(f)) if f == "quads"));
+    }
+
+    /// Verify that `--exact` makes the filter an exact match, regardless of its position.
+    #[test]
+    fn parse_exact_filter() {
+        let args = TestArgs::parse(vec!["--exact".to_string(), "quads".to_string()]);
+
+        assert!(matches!(args.filter, Some(TestFilter::Exact(f)) if f == "quads"));
+    }
+
+    /// Verify that `--regex` makes the filter a regular expression, even alongside `--exact`.
+    #[cfg(feature = "regex")]
+    #[test]
+    fn parse_regex_filter() {
+        let args = TestArgs::parse(vec![
+            "--exact".to_string(),
+            "^Render (quads|disks)$".to_string(),
+            "--regex".to_string(),
+        ]);
+
+        let filter = args.filter.unwrap();
+        assert!(matches!(filter, TestFilter::Regex(_)));
+        assert!(filter.matches("Render disks"));
+        assert!(!filter.matches("Render quads twice"));
+    }
+
+    /// Verify that `--regex` without the regex feature is an error rather than being ignored.
+    #[cfg(not(feature = "regex"))]
+    #[test]
+    #[should_panic(expected = "without the regex feature enabled")]
+    fn parse_regex_filter_without_feature() {
+        TestArgs::parse(vec!["quads".to_string(), "--regex".to_string()]);
+    }
+
+    /// Verify that `--list` is parsed.
+    #[test]
+    fn parse_list() {
+        assert!(TestArgs::parse(vec!["--list".to_string()]).list);
+        assert!(!TestArgs::parse(vec![]).list);
+    }
+
+    /// Verify that the tag expression is not mistaken for the filter.
+    #[test]
+    fn parse_tags() {
+        let args = TestArgs::parse(vec![
+            "--tags".to_string(),
+            "ui,!particles".to_string(),
+            "quads".to_string(),
+        ]);
+
+        assert_eq!(args.tags, Some(TagFilter::parse("ui,!particles")));
+        assert!(matches!(args.filter, Some(TestFilter::Substring(f)) if f == "quads"));
+
+        let args = TestArgs::parse(vec!["--tags=ui".to_string()]);
+        assert_eq!(args.tags, Some(TagFilter::parse("ui")));
+        assert!(args.filter.is_none());
+    }
+
+    /// Verify that the values of the Rust test harness's flags are not mistaken for the filter.
+    #[test]
+    fn parse_libtest_flag_values() {
+        let args = TestArgs::parse(vec![
+            "--test-threads".to_string(),
+            "4".to_string(),
+            "--skip".to_string(),
+            "disks".to_string(),
+            "--color=never".to_string(),
+            "quads".to_string(),
+        ]);
+
+        assert!(matches!(args.filter, Some(TestFilter::Substring(f)) if f == "quads"));
+
+        let args = TestArgs::parse(vec!["--test-threads".to_string(), "4".to_string()]);
+        assert!(args.filter.is_none());
+    }
+
+    /// Verify that there is no filter when no positional argument is passed.
+    #[test]
+    fn parse_no_filter() {
+        let args = TestArgs::parse(vec!["--exact".to_string()]);
+
+        assert!(args.filter.is_none());
+    }
+}
@@ -1,13 +1,54 @@
+pub use self::attachment::*;
+pub use self::baseline_comparison::*;
+pub use self::diff_line::DiffLine;
+pub use self::image_diff::*;
+#[cfg(feature = "serde")]
+pub use self::json_report::{JsonReportError, JSON_REPORT_VERSION};
+pub use self::test_case_duration::*;
+pub use self::test_case_panic::*;
 pub use self::test_case_result::*;
+pub use self::test_check::*;
+pub use self::test_failure::*;
+pub use self::test_group_results::*;
+pub use self::test_outcome::*;
+pub use self::trusted_html::TrustedHtml;
 
+#[cfg(feature = "serde")]
+pub(crate) use self::json_report::JsonReport;
+pub(crate) use self::test_case_panic::catch_test_case_panic;
+pub(crate) use self::trusted_html::escape_html;
+
+mod attachment;
+#[cfg(feature = "serde")]
+mod base64_bytes;
+mod baseline_comparison;
+mod diff_line;
+mod image_diff;
+#[cfg(feature = "serde")]
+mod json_report;
+mod test_case_duration;
+mod test_case_panic;
 mod test_case_result;
+mod test_check;
+mod test_failure;
+mod test_group_results;
+mod test_outcome;
+mod trusted_html;
+
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// The results of all of the test cases in the test suite that was run.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TestCaseResults {
     suite_title: String,
     suite_description: String,
     results: Vec<TestCaseResult>,
+    groups: Vec<TestGroupResults>,
+    filtered_out_count: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    duration: Option<Duration>,
 }
 
 impl TestCaseResults {
@@ -20,13 +61,36 @@ impl TestCaseResults {
         suite_description: String,
         results: Vec<TestCaseResult>,
     ) -> Self {
-        assert!(results.len() > 0);
+        TestCaseResults::with_groups(suite_title, suite_description, results, vec![])
+    }
 
-        TestCaseResults {
+    /// Create the results of a test suite that has groups of test cases.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no test case results in the suite or any of its groups.
+    pub fn with_groups(
+        suite_title: String,
+        suite_description: String,
+        results: Vec<TestCaseResult>,
+        groups: Vec<TestGroupResults>,
+    ) -> Self {
+        let test_case_results = TestCaseResults {
             suite_title,
             suite_description,
             results,
-        }
+            groups,
+            filtered_out_count: 0,
+            duration: None,
+        };
+
+        assert!(
+            test_case_results.all_results().next().is_some(),
+            r#"The "{}" test suite did not have any test case results."#,
+            test_case_results.suite_title
+        );
+
+        test_case_results
     }
 
     /// The test suite's title.
@@ -39,14 +103,75 @@ impl TestCaseResults {
         &self.suite_description
     }
 
-    /// The results of running all of the test cases.
+    /// The results of running the test cases that do not belong to a group.
+    ///
+    /// See [`TestCaseResults::all_results`] for the results of every test case.
     pub fn results(&self) -> &Vec<TestCaseResult> {
         &self.results
     }
 
-    /// Whether or not all of the test cases passed.
+    /// The results of each group of test cases.
+    pub fn groups(&self) -> &Vec<TestGroupResults> {
+        &self.groups
+    }
+
+    /// The results of every test case in the suite, including those in groups.
+    pub fn all_results(&self) -> Box<dyn Iterator<Item = &TestCaseResult> + '_> {
+        Box::new(
+            self.results
+                .iter()
+                .chain(self.groups.iter().flat_map(|group| group.all_results())),
+        )
+    }
+
+    /// Every test case's result along with the key that identifies it across runs of the suite,
+    /// in the same order as [`TestCaseResults::all_results`].
+    pub(crate) fn keyed_results(&self) -> Vec<(ResultKey<'_>, &TestCaseResult)> {
+        let mut keyed = vec![];
+        push_keyed_results(
+            &mut keyed,
+            &mut HashMap::new(),
+            &mut vec![],
+            &self.results,
+            &self.groups,
+        );
+        keyed
+    }
+
+    /// The number of test cases that were not run because they did not match a
+    /// [`crate::TestFilter`].
+    pub fn filtered_out_count(&self) -> usize {
+        self.filtered_out_count
+    }
+
+    /// Set the number of test cases that were not run because they did not match a
+    /// [`crate::TestFilter`].
+    pub fn set_filtered_out_count(&mut self, filtered_out_count: usize) {
+        self.filtered_out_count = filtered_out_count;
+    }
+
+    /// How long it took to run the entire test suite, if it was measured by the test runner.
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+
+    /// Set how long it took to run the entire test suite.
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = Some(duration);
+    }
+
+    /// Whether or not all of the test cases had an acceptable outcome.
+    ///
+    /// See [`TestOutcome::is_acceptable`].
     pub fn did_pass(&self) -> bool {
-        self.results.iter().all(|r| r.did_pass())
+        self.all_results().all(|r| r.did_pass())
+    }
+
+    /// The number of test cases that had the given outcome.
+    pub fn outcome_count(&self, outcome: TestOutcome) -> usize {
+        self.all_results()
+            .filter(|r| r.outcome() == outcome)
+            .count()
     }
 
     /// # Panics
@@ -56,3 +181,58 @@ impl TestCaseResults {
         assert!(self.did_pass());
     }
 }
+
+/// Identifies a test case's result across runs of the same suite, such as when comparing against
+/// a baseline, so that test cases that share a title but are in different groups are told apart.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ResultKey<'a> {
+    pub group_path: Vec<&'a str>,
+    pub title: &'a str,
+    /// How many earlier test cases in the same group have the same title.
+    pub occurrence: usize,
+}
+
+impl ResultKey<'_> {
+    /// The titles of the groups that the test case is in followed by its title, such as
+    /// "Chapter 1 / Render quads".
+    pub fn path(&self) -> String {
+        let mut path = self.group_path.clone();
+        path.push(self.title);
+        path.join(" / ")
+    }
+}
+
+/// Push the key of each result, counting the test cases seen so far by their group path and title
+/// to find each one's occurrence.
+fn push_keyed_results<'a>(
+    keyed: &mut Vec<(ResultKey<'a>, &'a TestCaseResult)>,
+    occurrences: &mut HashMap<(Vec<&'a str>, &'a str), usize>,
+    group_path: &mut Vec<&'a str>,
+    results: &'a [TestCaseResult],
+    groups: &'a [TestGroupResults],
+) {
+    for result in results {
+        let occurrence = occurrences
+            .entry((group_path.clone(), result.title()))
+            .or_insert(0);
+        let key = ResultKey {
+            group_path: group_path.clone(),
+            title: result.title(),
+            occurrence: *occurrence,
+        };
+        *occurrence += 1;
+        keyed.push((key, result));
+    }
+
+    for group in groups {
+        group_path.push(group.title());
+        push_keyed_results(
+            keyed,
+            occurrences,
+            group_path,
+            group.results(),
+            group.groups(),
+        );
+        group_path.pop();
+    }
+}
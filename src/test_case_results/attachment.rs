@@ -0,0 +1,80 @@
+/// A file that a test case produced, such as an image, a log or a buffer that it read back, which
+/// is kept alongside its result.
+///
+/// [`crate::ViewHtml`] shows images inline, text in a `<pre>` and links to download anything else.
+/// [`crate::HtmlReport`] writes each attachment to its own file.
+///
+/// ```
+/// # use conformer::Attachment;
+/// let log = Attachment::text("gpu.log".to_string(), "Out of memory".to_string());
+/// let pixels = Attachment::new(
+///     "pixels.rgba".to_string(),
+///     "application/octet-stream".to_string(),
+///     vec![255, 0, 0, 255],
+/// );
+///
+/// assert!(log.is_text());
+/// assert!(!pixels.is_text() && !pixels.is_image());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attachment {
+    name: String,
+    mime_type: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::test_case_results::base64_bytes")
+    )]
+    bytes: Vec<u8>,
+}
+
+impl Attachment {
+    /// Create an attachment named after the file that it should be saved as, such as
+    /// "framebuffer.png", along with its MIME type, such as "image/png".
+    pub fn new(name: String, mime_type: String, bytes: Vec<u8>) -> Self {
+        Attachment {
+            name,
+            mime_type,
+            bytes,
+        }
+    }
+
+    /// Create a UTF-8 plain text attachment, such as a log.
+    pub fn text(name: String, text: String) -> Self {
+        Attachment::new(
+            name,
+            "text/plain; charset=utf-8".to_string(),
+            text.into_bytes(),
+        )
+    }
+
+    /// The name of the file that the attachment should be saved as.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The attachment's MIME type, such as "image/png" or "text/plain".
+    pub fn mime_type(&self) -> &str {
+        &self.mime_type
+    }
+
+    /// The attachment's contents.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Whether or not the attachment is an image, which is any `image/` MIME type.
+    pub fn is_image(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+
+    /// Whether or not the attachment is text, which is any `text/` MIME type along with JSON and
+    /// XML.
+    pub fn is_text(&self) -> bool {
+        let essence = self.mime_type.split(';').next().unwrap_or("").trim();
+
+        essence.starts_with("text/")
+            || essence == "application/json"
+            || essence == "application/xml"
+    }
+}
@@ -0,0 +1,25 @@
+//! Serializes bytes, such as an attachment's contents, as a base64 string instead of an array of
+//! numbers, which is several times smaller in JSON reports.
+//!
+//! Arrays of numbers are still accepted when deserializing, so that reports that were written
+//! before bytes were encoded as base64 can be loaded.
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub(crate) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&base64::encode(bytes))
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Encoded {
+        Base64(String),
+        Numbers(Vec<u8>),
+    }
+
+    match Encoded::deserialize(deserializer)? {
+        Encoded::Base64(base64) => base64::decode(&base64).map_err(serde::de::Error::custom),
+        Encoded::Numbers(bytes) => Ok(bytes),
+    }
+}
@@ -0,0 +1,328 @@
+use crate::test_case_results::ResultKey;
+use crate::{TestCaseResult, TestCaseResults};
+use std::collections::{HashMap, HashSet};
+
+/// How a test case's result changed since a baseline run, such as the last green build.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BaselineChange {
+    /// The test case passed in the baseline but fails now.
+    Regressed,
+    /// The test case failed in the baseline but passes now.
+    Fixed,
+    /// The test case failed in the baseline and still fails.
+    StillFailing,
+    /// The test case passed in the baseline and still passes.
+    StillPassing,
+    /// The test case was not in the baseline.
+    New,
+    /// The test case was in the baseline but was not run this time.
+    Removed,
+}
+
+impl BaselineChange {
+    /// Every change, in the order that they are shown by visualizers.
+    pub const ALL: [BaselineChange; 6] = [
+        BaselineChange::Regressed,
+        BaselineChange::Fixed,
+        BaselineChange::StillFailing,
+        BaselineChange::StillPassing,
+        BaselineChange::New,
+        BaselineChange::Removed,
+    ];
+
+    /// A short, lowercase label for the change, such as "still failing".
+    pub fn label(&self) -> &'static str {
+        match self {
+            BaselineChange::Regressed => "regressed",
+            BaselineChange::Fixed => "fixed",
+            BaselineChange::StillFailing => "still failing",
+            BaselineChange::StillPassing => "still passing",
+            BaselineChange::New => "new",
+            BaselineChange::Removed => "removed",
+        }
+    }
+
+    /// Whether or not the test case's outcome is different from the baseline, as opposed to
+    /// staying the same.
+    pub fn is_change(&self) -> bool {
+        !matches!(
+            self,
+            BaselineChange::StillFailing | BaselineChange::StillPassing
+        )
+    }
+}
+
+/// The results of a test suite compared to the results of a previous run of the same suite,
+/// matching test cases by their titles and the titles of the groups that they are in.
+///
+/// A test case is considered to have passed if it had an acceptable outcome, see
+/// [`TestCaseResult::did_pass`].
+///
+/// ```
+/// # use conformer::{BaselineChange, BaselineComparison, TestCaseResult, TestCaseResults, TestOutcome};
+/// let results = |outcome| {
+///     let result = TestCaseResult::new("Render quads".to_string(), "".to_string(), outcome);
+///     TestCaseResults::new("Suite".to_string(), "".to_string(), vec![result])
+/// };
+/// let baseline = results(TestOutcome::Passed);
+/// let current = results(TestOutcome::Failed);
+///
+/// let comparison = BaselineComparison::new(&baseline, &current);
+///
+/// assert_eq!(comparison.change("Render quads"), Some(BaselineChange::Regressed));
+/// assert!(comparison.has_regressions());
+/// ```
+#[derive(Debug)]
+pub struct BaselineComparison<'a> {
+    entries: Vec<BaselineEntry<'a>>,
+}
+
+/// How one test case's result changed since the baseline.
+#[derive(Debug)]
+pub struct BaselineEntry<'a> {
+    key: ResultKey<'a>,
+    change: BaselineChange,
+    baseline: Option<&'a TestCaseResult>,
+    current: Option<&'a TestCaseResult>,
+}
+
+impl<'a> BaselineComparison<'a> {
+    /// Compare the current results to the baseline results, which are typically loaded from a
+    /// report that was saved by a previous run.
+    pub fn new(baseline: &'a TestCaseResults, current: &'a TestCaseResults) -> Self {
+        let baseline = baseline.keyed_results();
+        let current = current.keyed_results();
+
+        let baseline_by_key: HashMap<&ResultKey<'a>, &'a TestCaseResult> = baseline
+            .iter()
+            .map(|(key, result)| (key, *result))
+            .collect();
+        let current_keys: HashSet<&ResultKey<'a>> = current.iter().map(|(key, _)| key).collect();
+
+        let mut entries: Vec<BaselineEntry<'a>> = current
+            .iter()
+            .map(|(key, current)| {
+                let baseline = baseline_by_key.get(key).copied();
+
+                let change = match baseline {
+                    None => BaselineChange::New,
+                    Some(baseline) => match (baseline.did_pass(), current.did_pass()) {
+                        (true, false) => BaselineChange::Regressed,
+                        (false, true) => BaselineChange::Fixed,
+                        (false, false) => BaselineChange::StillFailing,
+                        (true, true) => BaselineChange::StillPassing,
+                    },
+                };
+
+                BaselineEntry {
+                    key: key.clone(),
+                    change,
+                    baseline,
+                    current: Some(current),
+                }
+            })
+            .collect();
+
+        for (key, baseline) in baseline.iter() {
+            if !current_keys.contains(key) {
+                entries.push(BaselineEntry {
+                    key: key.clone(),
+                    change: BaselineChange::Removed,
+                    baseline: Some(baseline),
+                    current: None,
+                });
+            }
+        }
+
+        BaselineComparison { entries }
+    }
+
+    /// Every test case in the current results, in order, followed by the test cases that were
+    /// removed since the baseline.
+    pub fn entries(&self) -> &[BaselineEntry<'a>] {
+        &self.entries
+    }
+
+    /// How the first test case with the given title changed since the baseline.
+    ///
+    /// Use [`BaselineComparison::entries`] to tell apart test cases that share a title.
+    pub fn change(&self, title: &str) -> Option<BaselineChange> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key.title == title)
+            .map(|entry| entry.change)
+    }
+
+    /// The number of test cases that had the given change.
+    pub fn change_count(&self, change: BaselineChange) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.change == change)
+            .count()
+    }
+
+    /// Whether or not any test cases passed in the baseline but fail now.
+    pub fn has_regressions(&self) -> bool {
+        self.change_count(BaselineChange::Regressed) > 0
+    }
+
+    /// Useful for failing a build only when something that used to pass is now broken, while
+    /// tolerating test cases that were already failing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one or more test cases regressed.
+    pub fn assert_no_regressions(&self) {
+        let regressed: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.change == BaselineChange::Regressed)
+            .map(|entry| entry.path())
+            .collect();
+
+        assert!(
+            regressed.is_empty(),
+            "{} test case(s) regressed since the baseline: {}",
+            regressed.len(),
+            regressed.join(", ")
+        );
+    }
+}
+
+impl<'a> BaselineEntry<'a> {
+    /// The test case's title.
+    pub fn title(&self) -> &'a str {
+        self.key.title
+    }
+
+    /// The titles of the groups that the test case is in, from the outermost group inwards.
+    pub fn group_path(&self) -> &[&'a str] {
+        &self.key.group_path
+    }
+
+    /// The titles of the groups that the test case is in followed by its title, such as
+    /// "Chapter 1 / Render quads".
+    pub fn path(&self) -> String {
+        self.key.path()
+    }
+
+    /// How the test case's result changed since the baseline.
+    pub fn change(&self) -> BaselineChange {
+        self.change
+    }
+
+    /// The test case's result in the baseline, unless it is new.
+    pub fn baseline(&self) -> Option<&'a TestCaseResult> {
+        self.baseline
+    }
+
+    /// The test case's current result, unless it was removed.
+    pub fn current(&self) -> Option<&'a TestCaseResult> {
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TestGroupResults, TestOutcome};
+
+    /// Verify that each test case is classified by how its outcome changed.
+    #[test]
+    fn classify() {
+        let results = |results: &[(&str, TestOutcome)]| {
+            let results = results
+                .iter()
+                .map(|(title, outcome)| {
+                    TestCaseResult::new(title.to_string(), "".to_string(), *outcome)
+                })
+                .collect();
+            TestCaseResults::new("Suite".to_string(), "".to_string(), results)
+        };
+
+        let baseline = results(&[
+            ("regressed", TestOutcome::Passed),
+            ("fixed", TestOutcome::Failed),
+            ("still failing", TestOutcome::Failed),
+            ("still passing", TestOutcome::Passed),
+            ("removed", TestOutcome::Passed),
+        ]);
+        let current = results(&[
+            ("regressed", TestOutcome::Failed),
+            ("fixed", TestOutcome::Passed),
+            ("still failing", TestOutcome::Failed),
+            ("still passing", TestOutcome::Passed),
+            ("new", TestOutcome::Failed),
+        ]);
+        let comparison = BaselineComparison::new(&baseline, &current);
+
+        for entry in comparison.entries() {
+            assert_eq!(entry.title(), entry.change().label());
+        }
+        assert_eq!(comparison.entries().len(), BaselineChange::ALL.len());
+        assert!(comparison.has_regressions());
+    }
+
+    /// Verify that test cases with the same title are matched by the groups that they are in, and
+    /// by their order within the same group.
+    #[test]
+    fn duplicate_titles() {
+        let results = |outcomes: [TestOutcome; 4]| {
+            let result =
+                |outcome| TestCaseResult::new("Quads".to_string(), "".to_string(), outcome);
+            let group = |title: &str, results| {
+                TestGroupResults::new(title.to_string(), "".to_string(), results, vec![])
+            };
+            TestCaseResults::with_groups(
+                "Suite".to_string(),
+                "".to_string(),
+                vec![result(outcomes[0])],
+                vec![
+                    group("Metal", vec![result(outcomes[1])]),
+                    group("CPU", vec![result(outcomes[2]), result(outcomes[3])]),
+                ],
+            )
+        };
+        let baseline = results([
+            TestOutcome::Passed,
+            TestOutcome::Failed,
+            TestOutcome::Passed,
+            TestOutcome::Failed,
+        ]);
+        let current = results([
+            TestOutcome::Passed,
+            TestOutcome::Passed,
+            TestOutcome::Failed,
+            TestOutcome::Failed,
+        ]);
+        let comparison = BaselineComparison::new(&baseline, &current);
+
+        let entries: Vec<(String, BaselineChange)> = comparison
+            .entries()
+            .iter()
+            .map(|entry| (entry.path(), entry.change()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("Quads".to_string(), BaselineChange::StillPassing),
+                ("Metal / Quads".to_string(), BaselineChange::Fixed),
+                ("CPU / Quads".to_string(), BaselineChange::Regressed),
+                ("CPU / Quads".to_string(), BaselineChange::StillFailing),
+            ]
+        );
+    }
+
+    /// Verify that test cases that were already failing do not count as regressions.
+    #[test]
+    fn still_failing_is_not_a_regression() {
+        let results = |outcome| {
+            let result = TestCaseResult::new("Render quads".to_string(), "".to_string(), outcome);
+            TestCaseResults::new("Suite".to_string(), "".to_string(), vec![result])
+        };
+        let baseline = results(TestOutcome::Failed);
+        let current = results(TestOutcome::Failed);
+
+        BaselineComparison::new(&baseline, &current).assert_no_regressions();
+    }
+}
@@ -0,0 +1,297 @@
+/// A line in a diff between an expected and an actual value.
+///
+/// See [`crate::TestFailure::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// A line that is the same in both values.
+    Same(String),
+    /// A line that is only in the expected value.
+    Expected(String),
+    /// A line that is only in the actual value.
+    Actual(String),
+    /// A number of lines that are the same in both values, which were left out because they are
+    /// not near a change.
+    Unchanged(usize),
+    /// A number of changed lines that were left out because there were too many to diff, which
+    /// follow the lines of the expected or actual value that were kept.
+    Omitted(usize),
+}
+
+/// How many unchanged lines are kept before and after each change.
+const CONTEXT_LINES: usize = 3;
+
+/// The most lines that can be added or removed before the values are considered too different
+/// to diff. Diffing takes time proportional to the number of lines times this.
+const MAX_EDITS: usize = 1000;
+
+/// How many lines of each value are kept when the values are too different to diff.
+const MAX_UNDIFFED_LINES: usize = 20;
+
+/// Diff the lines of the expected and actual values, using the fewest lines added and removed.
+///
+/// Only a few unchanged lines are kept around each change. Values that differ in too many lines
+/// to diff are shown one after the other instead, truncated to their first few lines.
+pub(crate) fn diff_lines(expected: &str, actual: &str) -> Vec<DiffLine> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    let prefix = expected
+        .iter()
+        .zip(actual.iter())
+        .take_while(|(e, a)| e == a)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(e, a)| e == a)
+        .count();
+    let expected_changed = &expected[prefix..expected.len() - suffix];
+    let actual_changed = &actual[prefix..actual.len() - suffix];
+
+    let mut diff: Vec<DiffLine> = expected[..prefix]
+        .iter()
+        .map(|l| DiffLine::Same(l.to_string()))
+        .collect();
+
+    match shortest_edit_diff(expected_changed, actual_changed) {
+        Some(changed_diff) => diff.extend(changed_diff),
+        None => {
+            diff.extend(truncated(expected_changed, DiffLine::Expected));
+            diff.extend(truncated(actual_changed, DiffLine::Actual));
+        }
+    }
+
+    diff.extend(
+        expected[expected.len() - suffix..]
+            .iter()
+            .map(|l| DiffLine::Same(l.to_string())),
+    );
+
+    collapse_unchanged(diff)
+}
+
+/// Myers' diff algorithm, which follows the diagonals of the edit graph to find the fewest lines
+/// to remove from the expected value and add from the actual value.
+///
+/// Returns `None` if more than [`MAX_EDITS`] lines would need to be added or removed.
+fn shortest_edit_diff(expected: &[&str], actual: &[&str]) -> Option<Vec<DiffLine>> {
+    let (n, m) = (expected.len() as isize, actual.len() as isize);
+
+    // furthest[d][(k + d) / 2] is how far along the expected value the path with d edits that
+    // ends on diagonal k = x - y gets.
+    let mut furthest: Vec<Vec<isize>> = vec![];
+
+    for d in 0..=(MAX_EDITS as isize).min(n + m) {
+        let mut current = vec![0; d as usize + 1];
+
+        for k in (-d..=d).step_by(2) {
+            let mut x = if d == 0 {
+                0
+            } else {
+                let previous = &furthest[d as usize - 1];
+                let from_above = |k: isize| previous[((k + d - 1) / 2) as usize];
+
+                // Move down (add an actual line) from diagonal k + 1, or right (remove an
+                // expected line) from diagonal k - 1, whichever got further.
+                if k == -d || (k != d && from_above(k - 1) < from_above(k + 1)) {
+                    from_above(k + 1)
+                } else {
+                    from_above(k - 1) + 1
+                }
+            };
+            let mut y = x - k;
+
+            while x < n && y < m && expected[x as usize] == actual[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            current[((k + d) / 2) as usize] = x;
+
+            if x >= n && y >= m {
+                furthest.push(current);
+                return Some(backtrack(expected, actual, &furthest));
+            }
+        }
+
+        furthest.push(current);
+    }
+
+    None
+}
+
+/// Follow the furthest reaching paths back from the end of both values to their start.
+fn backtrack(expected: &[&str], actual: &[&str], furthest: &[Vec<isize>]) -> Vec<DiffLine> {
+    let mut diff = vec![];
+    let (mut x, mut y) = (expected.len() as isize, actual.len() as isize);
+
+    for d in (1..furthest.len() as isize).rev() {
+        let k = x - y;
+        let previous = &furthest[d as usize - 1];
+        let from_above = |k: isize| previous[((k + d - 1) / 2) as usize];
+
+        let down = k == -d || (k != d && from_above(k - 1) < from_above(k + 1));
+        let previous_k = if down { k + 1 } else { k - 1 };
+        let previous_x = from_above(previous_k);
+        let previous_y = previous_x - previous_k;
+
+        let (edit_x, edit_y) = if down {
+            (previous_x, previous_y + 1)
+        } else {
+            (previous_x + 1, previous_y)
+        };
+        while x > edit_x && y > edit_y {
+            x -= 1;
+            y -= 1;
+            diff.push(DiffLine::Same(expected[x as usize].to_string()));
+        }
+
+        if down {
+            diff.push(DiffLine::Actual(actual[previous_y as usize].to_string()));
+        } else {
+            diff.push(DiffLine::Expected(
+                expected[previous_x as usize].to_string(),
+            ));
+        }
+        x = previous_x;
+        y = previous_y;
+    }
+
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        diff.push(DiffLine::Same(expected[x as usize].to_string()));
+    }
+
+    diff.reverse();
+    diff
+}
+
+/// The first few lines, followed by how many were left out.
+fn truncated(lines: &[&str], diff_line: fn(String) -> DiffLine) -> Vec<DiffLine> {
+    let mut diff: Vec<DiffLine> = lines
+        .iter()
+        .take(MAX_UNDIFFED_LINES)
+        .map(|l| diff_line(l.to_string()))
+        .collect();
+
+    if lines.len() > MAX_UNDIFFED_LINES {
+        diff.push(DiffLine::Omitted(lines.len() - MAX_UNDIFFED_LINES));
+    }
+
+    diff
+}
+
+/// Replace the unchanged lines that are not within a few lines of a change with how many there
+/// were.
+fn collapse_unchanged(diff: Vec<DiffLine>) -> Vec<DiffLine> {
+    let mut collapsed = vec![];
+    let mut unchanged = vec![];
+
+    for line in diff {
+        if let DiffLine::Same(_) = line {
+            unchanged.push(line);
+            continue;
+        }
+
+        // Keep the lines after the previous change and before this one.
+        let keep_before = if collapsed.is_empty() {
+            0
+        } else {
+            CONTEXT_LINES
+        };
+        push_unchanged(&mut collapsed, &mut unchanged, keep_before, CONTEXT_LINES);
+        collapsed.push(line);
+    }
+
+    let keep_before = if collapsed.is_empty() {
+        0
+    } else {
+        CONTEXT_LINES
+    };
+    push_unchanged(&mut collapsed, &mut unchanged, keep_before, 0);
+
+    collapsed
+}
+
+fn push_unchanged(
+    collapsed: &mut Vec<DiffLine>,
+    unchanged: &mut Vec<DiffLine>,
+    keep_before: usize,
+    keep_after: usize,
+) {
+    if unchanged.len() <= keep_before + keep_after {
+        collapsed.append(unchanged);
+        return;
+    }
+
+    let after = unchanged.split_off(unchanged.len() - keep_after);
+    let skipped = unchanged.len() - keep_before;
+    unchanged.truncate(keep_before);
+
+    collapsed.append(unchanged);
+    collapsed.push(DiffLine::Unchanged(skipped));
+    collapsed.extend(after);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that changed, removed and added lines are found.
+    #[test]
+    fn diff() {
+        let diff = diff_lines("a\nb\nc\nd", "a\nx\nc\nd\ne");
+
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Expected("b".to_string()),
+                DiffLine::Actual("x".to_string()),
+                DiffLine::Same("c".to_string()),
+                DiffLine::Same("d".to_string()),
+                DiffLine::Actual("e".to_string()),
+            ]
+        );
+    }
+
+    /// Verify that only a few unchanged lines are kept around each change.
+    #[test]
+    fn collapses_unchanged_lines() {
+        let expected: Vec<String> = (0..20).map(|n| n.to_string()).collect();
+        let mut actual = expected.clone();
+        actual[10] = "ten".to_string();
+
+        let diff = diff_lines(&expected.join("\n"), &actual.join("\n"));
+
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Unchanged(7),
+                DiffLine::Same("7".to_string()),
+                DiffLine::Same("8".to_string()),
+                DiffLine::Same("9".to_string()),
+                DiffLine::Expected("10".to_string()),
+                DiffLine::Actual("ten".to_string()),
+                DiffLine::Same("11".to_string()),
+                DiffLine::Same("12".to_string()),
+                DiffLine::Same("13".to_string()),
+                DiffLine::Unchanged(6),
+            ]
+        );
+    }
+
+    /// Verify that values that differ in too many lines are truncated instead of diffed.
+    #[test]
+    fn too_many_changes_to_diff() {
+        let expected = vec!["expected"; 2000].join("\n");
+        let actual = vec!["actual"; 2000].join("\n");
+
+        let diff = diff_lines(&expected, &actual);
+
+        assert_eq!(diff.len(), 2 * (MAX_UNDIFFED_LINES + 1));
+        assert_eq!(diff[MAX_UNDIFFED_LINES], DiffLine::Omitted(1980));
+        assert_eq!(diff[diff.len() - 1], DiffLine::Omitted(1980));
+    }
+}
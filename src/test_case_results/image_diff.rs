@@ -0,0 +1,141 @@
+use crate::{TestCheck, TestFailure};
+
+/// The result of comparing an image that a test case produced against the image that it
+/// expected, along with the images themselves so that they can be shown side by side.
+///
+/// Images are stored as PNGs, which [`crate::ViewHtml`] embeds in its page and
+/// [`crate::HtmlReport`] writes to their own files. An image without any pixels has no PNG bytes.
+/// See `ImageComparison`, which requires the `images` feature, for creating one.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageDiff {
+    name: String,
+    did_pass: bool,
+    pixel_count: usize,
+    differing_pixel_count: usize,
+    max_channel_delta: u8,
+    max_perceptual_delta: f64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::test_case_results::base64_bytes")
+    )]
+    expected_png: Vec<u8>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::test_case_results::base64_bytes")
+    )]
+    actual_png: Vec<u8>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::test_case_results::base64_bytes")
+    )]
+    diff_png: Vec<u8>,
+}
+
+/// The statistics of an [`ImageDiff`], which are used to decide whether or not it passed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ImageDiffStats {
+    /// Whether or not the images were close enough to be considered the same.
+    pub did_pass: bool,
+    /// The number of pixels in the larger of the two images.
+    pub pixel_count: usize,
+    /// The number of pixels that were not within the tolerance.
+    pub differing_pixel_count: usize,
+    /// The largest difference of any channel of any pixel, from 0 to 255.
+    pub max_channel_delta: u8,
+    /// The largest perceptual difference of any pixel, from 0.0 to 1.0.
+    pub max_perceptual_delta: f64,
+}
+
+impl ImageDiffStats {
+    /// A short summary of the statistics, such as
+    /// "12 of 65536 pixels differ; max channel delta 40; max perceptual delta 0.120".
+    pub fn summary(&self) -> String {
+        format!(
+            "{} of {} pixels differ; max channel delta {}; max perceptual delta {:.3}",
+            self.differing_pixel_count,
+            self.pixel_count,
+            self.max_channel_delta,
+            self.max_perceptual_delta
+        )
+    }
+}
+
+impl ImageDiff {
+    /// Create an image diff from its statistics and the PNGs of the expected image, the actual
+    /// image and an image that highlights the pixels that differ.
+    pub fn new(
+        name: String,
+        stats: ImageDiffStats,
+        expected_png: Vec<u8>,
+        actual_png: Vec<u8>,
+        diff_png: Vec<u8>,
+    ) -> Self {
+        ImageDiff {
+            name,
+            did_pass: stats.did_pass,
+            pixel_count: stats.pixel_count,
+            differing_pixel_count: stats.differing_pixel_count,
+            max_channel_delta: stats.max_channel_delta,
+            max_perceptual_delta: stats.max_perceptual_delta,
+            expected_png,
+            actual_png,
+            diff_png,
+        }
+    }
+
+    /// What was compared, such as "Framebuffer".
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The statistics that were used to decide whether or not the images matched.
+    pub fn stats(&self) -> ImageDiffStats {
+        ImageDiffStats {
+            did_pass: self.did_pass,
+            pixel_count: self.pixel_count,
+            differing_pixel_count: self.differing_pixel_count,
+            max_channel_delta: self.max_channel_delta,
+            max_perceptual_delta: self.max_perceptual_delta,
+        }
+    }
+
+    /// Whether or not the images were close enough to be considered the same.
+    pub fn did_pass(&self) -> bool {
+        self.did_pass
+    }
+
+    /// The PNG of the image that the test case expected.
+    pub fn expected_png(&self) -> &[u8] {
+        &self.expected_png
+    }
+
+    /// The PNG of the image that the test case produced.
+    pub fn actual_png(&self) -> &[u8] {
+        &self.actual_png
+    }
+
+    /// The PNG of an image that highlights the pixels that differ.
+    pub fn diff_png(&self) -> &[u8] {
+        &self.diff_png
+    }
+
+    /// A short summary of the statistics, see [`ImageDiffStats::summary`].
+    pub fn summary(&self) -> String {
+        self.stats().summary()
+    }
+
+    /// A check named after the image diff, which fails with the summary if the images did not
+    /// match.
+    pub fn to_check(&self) -> TestCheck {
+        if self.did_pass {
+            TestCheck::passed(self.name.clone())
+        } else {
+            TestCheck::failed(
+                self.name.clone(),
+                TestFailure::new(format!("{} did not match the expected image.", self.name))
+                    .with_details(self.summary()),
+            )
+        }
+    }
+}
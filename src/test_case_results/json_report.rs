@@ -0,0 +1,226 @@
+use crate::TestCaseResults;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// The version of the JSON report format that is written by [`crate::ViewJson`].
+///
+/// This is increased whenever a change to the format would prevent older reports from being
+/// loaded, so that archived reports fail to load with a clear error instead of being misread.
+pub const JSON_REPORT_VERSION: u32 = 1;
+
+/// A versioned JSON report of a test suite's results.
+#[derive(Serialize)]
+pub(crate) struct JsonReport<'a> {
+    version: u32,
+    results: &'a TestCaseResults,
+}
+
+impl<'a> JsonReport<'a> {
+    pub(crate) fn new(results: &'a TestCaseResults) -> Self {
+        JsonReport {
+            version: JSON_REPORT_VERSION,
+            results,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonReportVersion {
+    version: u32,
+}
+
+#[derive(Deserialize)]
+struct OwnedJsonReport {
+    results: TestCaseResults,
+}
+
+/// An error while loading a JSON report using [`TestCaseResults::from_json`].
+#[derive(Debug)]
+pub enum JsonReportError {
+    /// The report was not valid JSON, or did not contain test results.
+    Json(serde_json::Error),
+    /// The report was written using a version of the format that is not supported.
+    UnsupportedVersion(u32),
+    /// The report did not have any test case results, which [`TestCaseResults`] requires.
+    NoTestCaseResults,
+}
+
+impl Display for JsonReportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonReportError::Json(err) => write!(f, "Invalid JSON report: {}", err),
+            JsonReportError::UnsupportedVersion(version) => write!(
+                f,
+                "JSON report version {} is not supported, expected version {}",
+                version, JSON_REPORT_VERSION
+            ),
+            JsonReportError::NoTestCaseResults => {
+                write!(f, "JSON report does not have any test case results")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JsonReportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonReportError::Json(err) => Some(err),
+            JsonReportError::UnsupportedVersion(_) | JsonReportError::NoTestCaseResults => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonReportError {
+    fn from(err: serde_json::Error) -> Self {
+        JsonReportError::Json(err)
+    }
+}
+
+impl TestCaseResults {
+    /// Load test results from a JSON report that was written by [`crate::ViewJson`].
+    ///
+    /// The loaded results can be rendered by any visualizer.
+    pub fn from_json(json: &str) -> Result<TestCaseResults, JsonReportError> {
+        let version: JsonReportVersion = serde_json::from_str(json)?;
+        if version.version != JSON_REPORT_VERSION {
+            return Err(JsonReportError::UnsupportedVersion(version.version));
+        }
+
+        let report: OwnedJsonReport = serde_json::from_str(json)?;
+        if report.results.all_results().next().is_none() {
+            return Err(JsonReportError::NoTestCaseResults);
+        }
+
+        Ok(report.results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Attachment, ImageDiff, ImageDiffStats, TestCasePanic, TestCaseResult, TestGroupResults,
+        TestOutcome, ViewJson,
+    };
+    use std::time::Duration;
+
+    /// Verify that results, including groups, panics, timeouts and metadata, can be written to a
+    /// JSON report and loaded back.
+    #[test]
+    fn round_trip() {
+        let mut passed = TestCaseResult::new(
+            "Render quads".to_string(),
+            "Quads are rendered.".to_string(),
+            TestOutcome::Passed,
+        );
+        passed.set_tags(vec!["ui".to_string()]);
+        passed.insert_metadata("renderer".to_string(), "metal".to_string());
+
+        let group = TestGroupResults::new(
+            "Chapter 1".to_string(),
+            "".to_string(),
+            vec![
+                TestCaseResult::panicked(
+                    "Render disks".to_string(),
+                    "".to_string(),
+                    TestCasePanic::new("oh no".to_string(), Some("src/lib.rs:1:1".to_string())),
+                ),
+                TestCaseResult::timed_out(
+                    "Render text".to_string(),
+                    "".to_string(),
+                    Duration::from_millis(1500),
+                ),
+            ],
+            vec![],
+        );
+        let mut results = TestCaseResults::with_groups(
+            "My Test Suite".to_string(),
+            "".to_string(),
+            vec![passed],
+            vec![group],
+        );
+        results.set_filtered_out_count(2);
+
+        let json = ViewJson::new().process_test_results(&results);
+
+        assert_eq!(TestCaseResults::from_json(&json).unwrap(), results);
+    }
+
+    /// Verify that attachments are written as base64, and that reports which wrote them as arrays
+    /// of numbers can still be loaded.
+    #[test]
+    fn attachments_are_base64() {
+        let mut result = TestCaseResult::new(
+            "Render quads".to_string(),
+            "".to_string(),
+            TestOutcome::Failed,
+        );
+        result.push_attachment(Attachment::text("gpu.log".to_string(), "OOM".to_string()));
+        let results =
+            TestCaseResults::new("My Test Suite".to_string(), "".to_string(), vec![result]);
+
+        let json = ViewJson::new().process_test_results(&results);
+        assert!(json.contains(r#""bytes": "T09N""#));
+        assert_eq!(TestCaseResults::from_json(&json).unwrap(), results);
+
+        let legacy_json = json.replace(r#""bytes": "T09N""#, r#""bytes": [79, 79, 77]"#);
+        assert_eq!(TestCaseResults::from_json(&legacy_json).unwrap(), results);
+    }
+
+    /// Verify that an image diff's PNGs are written as base64.
+    #[test]
+    fn image_diffs_are_base64() {
+        let stats = ImageDiffStats {
+            did_pass: false,
+            pixel_count: 1,
+            differing_pixel_count: 1,
+            max_channel_delta: 255,
+            max_perceptual_delta: 1.0,
+        };
+        let mut result = TestCaseResult::new(
+            "Render quads".to_string(),
+            "".to_string(),
+            TestOutcome::Failed,
+        );
+        result.push_image_diff(ImageDiff::new(
+            "Framebuffer".to_string(),
+            stats,
+            b"expected".to_vec(),
+            b"actual".to_vec(),
+            b"diff".to_vec(),
+        ));
+        let results =
+            TestCaseResults::new("My Test Suite".to_string(), "".to_string(), vec![result]);
+
+        let json = ViewJson::new().process_test_results(&results);
+        assert!(json.contains(r#""expected_png": "ZXhwZWN0ZWQ=""#));
+        assert!(json.contains(r#""diff_png": "ZGlmZg==""#));
+        assert_eq!(TestCaseResults::from_json(&json).unwrap(), results);
+    }
+
+    /// Verify that a report without any test case results is not loaded, since
+    /// [`TestCaseResults`] always has at least one.
+    #[test]
+    fn no_test_case_results() {
+        let json = format!(
+            r#"{{"version": {}, "results": {{"suite_title": "My Test Suite", "suite_description": "", "results": [], "groups": [], "filtered_out_count": 0}}}}"#,
+            JSON_REPORT_VERSION
+        );
+
+        match TestCaseResults::from_json(&json) {
+            Err(JsonReportError::NoTestCaseResults) => {}
+            other => panic!("{:?}", other),
+        };
+    }
+
+    /// Verify that reports written using a different version of the format are not loaded.
+    #[test]
+    fn unsupported_version() {
+        let json = r#"{"version": 999, "results": {}}"#;
+
+        match TestCaseResults::from_json(json) {
+            Err(JsonReportError::UnsupportedVersion(999)) => {}
+            other => panic!("{:?}", other),
+        };
+    }
+}
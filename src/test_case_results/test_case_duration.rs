@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+/// How long a test case took to run, measured by the test runner.
+///
+/// Creating the type to test (such as a renderer) is measured separately from running the test
+/// case, so that slow setup can be told apart from a slow test case.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestCaseDuration {
+    setup: Duration,
+    run: Duration,
+}
+
+impl TestCaseDuration {
+    #[allow(missing_docs)]
+    pub fn new(setup: Duration, run: Duration) -> Self {
+        TestCaseDuration { setup, run }
+    }
+
+    /// How long it took to create the type to test.
+    pub fn setup(&self) -> Duration {
+        self.setup
+    }
+
+    /// How long it took to run the test case.
+    pub fn run(&self) -> Duration {
+        self.run
+    }
+
+    /// How long it took to set up and run the test case.
+    pub fn total(&self) -> Duration {
+        self.setup + self.run
+    }
+}
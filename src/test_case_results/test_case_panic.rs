@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::panic::{AssertUnwindSafe, PanicHookInfo};
+use std::sync::Once;
+
+thread_local! {
+    /// Set while a test case is being run under [`catch_test_case_panic`] on this thread.
+    /// Holds the most recently captured panic, if any.
+    static CAPTURED_PANIC: RefCell<Option<Option<TestCasePanic>>> = const { RefCell::new(None) };
+}
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+/// Information about a test case that panicked while it was being run.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestCasePanic {
+    message: String,
+    location: Option<String>,
+}
+
+impl TestCasePanic {
+    #[allow(missing_docs)]
+    pub fn new(message: String, location: Option<String>) -> Self {
+        TestCasePanic { message, location }
+    }
+
+    /// The panic message, such as `called `Option::unwrap()` on a `None` value`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The file, line and column where the panic occurred, such as `src/lib.rs:10:5`.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+}
+
+impl Display for TestCasePanic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at '{}', {}", self.message, location),
+            None => write!(f, "panicked at '{}'", self.message),
+        }
+    }
+}
+
+/// Call the function, converting a panic into a [`TestCasePanic`].
+///
+/// Panics that are caught here are not printed to stderr, since they'll be reported by the
+/// visualizers. Panics outside of this function are forwarded to the previously installed hook.
+pub(crate) fn catch_test_case_panic<R>(f: impl FnOnce() -> R) -> Result<R, TestCasePanic> {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |info| {
+            let is_capturing = CAPTURED_PANIC.with(|captured| {
+                let mut captured = captured.borrow_mut();
+                match captured.as_mut() {
+                    Some(slot) => {
+                        *slot = Some(panic_from_hook_info(info));
+                        true
+                    }
+                    None => false,
+                }
+            });
+
+            if !is_capturing {
+                previous_hook(info);
+            }
+        }));
+    });
+
+    let previously_capturing = CAPTURED_PANIC.with(|captured| captured.replace(Some(None)));
+
+    let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+
+    let captured = CAPTURED_PANIC.with(|captured| captured.replace(previously_capturing));
+
+    result.map_err(|payload| match captured.flatten() {
+        Some(panic) => panic,
+        None => TestCasePanic::new(payload_to_string(payload.as_ref()), None),
+    })
+}
+
+fn panic_from_hook_info(info: &PanicHookInfo) -> TestCasePanic {
+    TestCasePanic::new(
+        payload_to_string(info.payload()),
+        info.location().map(|location| location.to_string()),
+    )
+}
+
+fn payload_to_string(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
@@ -1,26 +1,85 @@
+use crate::{
+    Attachment, ImageDiff, TestCaseDuration, TestCasePanic, TestCheck, TestFailure, TestOutcome,
+    TrustedHtml,
+};
 use std::collections::HashMap;
+use std::time::Duration;
 
-/// Whether or not the test case passed along with metadata that they results renderer can
+/// The outcome of the test case along with metadata that they results renderer can
 /// make use of when rendering our the results of the conformance test.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TestCaseResult {
     title: String,
     description: String,
-    did_pass: bool,
+    outcome: TestOutcome,
+    #[cfg_attr(feature = "serde", serde(default))]
+    failures: Vec<TestFailure>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    checks: Vec<TestCheck>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    image_diffs: Vec<ImageDiff>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    attachments: Vec<Attachment>,
+    panic: Option<TestCasePanic>,
+    timeout: Option<Duration>,
+    tags: Vec<String>,
     metadata: HashMap<String, String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    html_visual: Option<TrustedHtml>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    duration: Option<TestCaseDuration>,
 }
 
 impl TestCaseResult {
-    #[allow(missing_docs)]
-    pub fn new(title: String, description: String, did_pass: bool) -> Self {
+    /// Create a result with the given outcome, or with whether or not the test case passed.
+    ///
+    /// ```
+    /// # use conformer::{TestCaseResult, TestOutcome};
+    /// let skipped = TestCaseResult::new("Render quads".to_string(), "".to_string(), TestOutcome::Skipped);
+    /// let failed = TestCaseResult::new("Render disks".to_string(), "".to_string(), false);
+    ///
+    /// assert_eq!(failed.outcome(), TestOutcome::Failed);
+    /// ```
+    pub fn new(title: String, description: String, outcome: impl Into<TestOutcome>) -> Self {
         TestCaseResult {
             title,
             description,
-            did_pass,
+            outcome: outcome.into(),
+            failures: vec![],
+            checks: vec![],
+            image_diffs: vec![],
+            attachments: vec![],
+            panic: None,
+            timeout: None,
+            tags: vec![],
             metadata: HashMap::new(),
+            html_visual: None,
+            duration: None,
         }
     }
 
+    /// Create a failed result for a test case, along with why it failed.
+    pub fn failed(title: String, description: String, failure: TestFailure) -> Self {
+        let mut result = TestCaseResult::new(title, description, TestOutcome::Failed);
+        result.failures.push(failure);
+        result
+    }
+
+    /// Create a failed result for a test case that panicked while it was being run.
+    pub fn panicked(title: String, description: String, panic: TestCasePanic) -> Self {
+        let mut result = TestCaseResult::new(title, description, TestOutcome::Failed);
+        result.panic = Some(panic);
+        result
+    }
+
+    /// Create a failed result for a test case that did not finish within its timeout.
+    pub fn timed_out(title: String, description: String, timeout: Duration) -> Self {
+        let mut result = TestCaseResult::new(title, description, TestOutcome::Failed);
+        result.timeout = Some(timeout);
+        result
+    }
+
     /// The test case's title.
     pub fn title(&self) -> &str {
         &self.title
@@ -31,14 +90,128 @@ impl TestCaseResult {
         &self.description
     }
 
+    /// The tags of the test case, copied from [`crate::TestCase::tags`] by the test runner.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Set the tags of the test case.
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+    }
+
     /// Insert metadata for the test case result.
     pub fn insert_metadata(&mut self, key: String, value: String) -> Option<String> {
         self.metadata.insert(key, value)
     }
 
-    /// Describes why the test passed or failed.
+    /// Set the HTML that [`crate::ViewHtml`] shows to visualize what the test case did, such as
+    /// the image that a renderer produced.
+    ///
+    /// Unlike the rest of the test case's text, the visual is not escaped.
+    pub fn set_html_visual(&mut self, html_visual: TrustedHtml) {
+        self.html_visual = Some(html_visual);
+    }
+
+    /// The HTML that visualizes what the test case did, if the test case set one.
+    pub fn html_visual(&self) -> Option<&TrustedHtml> {
+        self.html_visual.as_ref()
+    }
+
+    /// Whether or not the test case's outcome allows the test suite to pass.
+    ///
+    /// See [`TestOutcome::is_acceptable`].
     pub fn did_pass(&self) -> bool {
-        self.did_pass
+        self.outcome.is_acceptable()
+    }
+
+    /// The outcome of the test case.
+    pub fn outcome(&self) -> TestOutcome {
+        self.outcome
+    }
+
+    /// Set the outcome of the test case.
+    pub fn set_outcome(&mut self, outcome: TestOutcome) {
+        self.outcome = outcome;
+    }
+
+    /// The reasons that the test case failed, such as each of the checks that failed.
+    ///
+    /// Empty if the test case passed or did not provide a reason.
+    pub fn failures(&self) -> &[TestFailure] {
+        &self.failures
+    }
+
+    /// Add a reason that the test case failed.
+    pub fn push_failure(&mut self, failure: TestFailure) {
+        self.failures.push(failure);
+    }
+
+    /// The named checks that the test case made, in the order that they were made.
+    pub fn checks(&self) -> &[TestCheck] {
+        &self.checks
+    }
+
+    /// Add a named check that the test case made.
+    ///
+    /// A test case that passed is considered to have failed if any of its checks failed.
+    pub fn push_check(&mut self, check: TestCheck) {
+        if !check.did_pass() && self.outcome == TestOutcome::Passed {
+            self.outcome = TestOutcome::Failed;
+        }
+
+        self.checks.push(check);
+    }
+
+    /// The images that the test case compared, such as its framebuffer against a golden image.
+    pub fn image_diffs(&self) -> &[ImageDiff] {
+        &self.image_diffs
+    }
+
+    /// Add an image that the test case compared, along with its check.
+    ///
+    /// See [`ImageDiff::to_check`] and [`TestCaseResult::push_check`].
+    pub fn push_image_diff(&mut self, image_diff: ImageDiff) {
+        self.push_check(image_diff.to_check());
+        self.image_diffs.push(image_diff);
+    }
+
+    /// The files that the test case produced, such as logs.
+    pub fn attachments(&self) -> &[Attachment] {
+        &self.attachments
+    }
+
+    /// Keep a file that the test case produced alongside its result.
+    pub fn push_attachment(&mut self, attachment: Attachment) {
+        self.attachments.push(attachment);
+    }
+
+    /// The reasons that the test case failed, followed by the reasons that each of its failed
+    /// checks failed.
+    pub fn all_failures(&self) -> impl Iterator<Item = &TestFailure> {
+        self.failures
+            .iter()
+            .chain(self.checks.iter().filter_map(|check| check.failure()))
+    }
+
+    /// Information about the panic, if the test case panicked while it was being run.
+    pub fn panic(&self) -> Option<&TestCasePanic> {
+        self.panic.as_ref()
+    }
+
+    /// The timeout that the test case exceeded, if it timed out.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// How long the test case took, if it was measured by the test runner.
+    pub fn duration(&self) -> Option<TestCaseDuration> {
+        self.duration
+    }
+
+    /// Set how long the test case took.
+    pub fn set_duration(&mut self, duration: TestCaseDuration) {
+        self.duration = Some(duration);
     }
 
     /// Information that the test runner stored about the test case.
@@ -0,0 +1,44 @@
+use crate::TestFailure;
+
+/// One of the named checks that a test case made, such as checking one region of a framebuffer.
+///
+/// See [`crate::TestCaseResult::push_check`] and [`crate::Assertions`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestCheck {
+    name: String,
+    failure: Option<TestFailure>,
+}
+
+impl TestCheck {
+    /// Create a check that passed.
+    pub fn passed(name: String) -> Self {
+        TestCheck {
+            name,
+            failure: None,
+        }
+    }
+
+    /// Create a check that failed, along with why it failed.
+    pub fn failed(name: String, failure: TestFailure) -> Self {
+        TestCheck {
+            name,
+            failure: Some(failure),
+        }
+    }
+
+    /// The check's name, such as "top left quadrant".
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether or not the check passed.
+    pub fn did_pass(&self) -> bool {
+        self.failure.is_none()
+    }
+
+    /// Why the check failed, if it failed.
+    pub fn failure(&self) -> Option<&TestFailure> {
+        self.failure.as_ref()
+    }
+}
@@ -0,0 +1,147 @@
+use crate::test_case_results::diff_line::diff_lines;
+use crate::DiffLine;
+use std::fmt::{Debug, Display, Formatter};
+
+/// Values whose [`Debug`] representation is longer than this are pretty printed over multiple
+/// lines, so that they can be diffed line by line.
+const MAX_SINGLE_LINE_LEN: usize = 80;
+
+/// Why a test case failed, such as a message along with the expected and actual values.
+///
+/// ```
+/// # use conformer::TestFailure;
+/// let failure = TestFailure::new("The first pixel was the wrong color.".to_string())
+///     .with_expected_actual(&[0, 0, 255, 255], &[255, 0, 0, 255]);
+///
+/// assert_eq!(failure.expected(), Some("[0, 0, 255, 255]"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestFailure {
+    message: String,
+    details: Option<String>,
+    expected: Option<String>,
+    actual: Option<String>,
+}
+
+impl TestFailure {
+    #[allow(missing_docs)]
+    pub fn new(message: String) -> Self {
+        TestFailure {
+            message,
+            details: None,
+            expected: None,
+            actual: None,
+        }
+    }
+
+    /// Add more information about the failure, which can span multiple lines.
+    pub fn with_details(mut self, details: String) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    /// Add the value that the test case expected and the value that it actually got, using
+    /// their [`Debug`] representations.
+    ///
+    /// Values that are too long to fit on one line are pretty printed using `{:#?}`, so that
+    /// they can be shown as a diff. See [`TestFailure::diff`].
+    pub fn with_expected_actual<T: Debug + ?Sized>(mut self, expected: &T, actual: &T) -> Self {
+        let expected_compact = format!("{:?}", expected);
+        let actual_compact = format!("{:?}", actual);
+
+        if expected_compact.len().max(actual_compact.len()) > MAX_SINGLE_LINE_LEN {
+            self.expected = Some(format!("{:#?}", expected));
+            self.actual = Some(format!("{:#?}", actual));
+        } else {
+            self.expected = Some(expected_compact);
+            self.actual = Some(actual_compact);
+        }
+
+        self
+    }
+
+    /// Add the text that the test case expected and the text that it actually got.
+    ///
+    /// Multi-line text is shown as a diff. See [`TestFailure::diff`].
+    pub fn with_expected_actual_text(mut self, expected: String, actual: String) -> Self {
+        self.expected = Some(expected);
+        self.actual = Some(actual);
+        self
+    }
+
+    /// A short explanation of why the test case failed.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// More information about the failure.
+    pub fn details(&self) -> Option<&str> {
+        self.details.as_deref()
+    }
+
+    /// The value that the test case expected.
+    pub fn expected(&self) -> Option<&str> {
+        self.expected.as_deref()
+    }
+
+    /// The value that the test case actually got.
+    pub fn actual(&self) -> Option<&str> {
+        self.actual.as_deref()
+    }
+
+    /// A line by line diff of the expected and actual values, if either of them spans multiple
+    /// lines.
+    ///
+    /// Single line values are easier to compare when shown one above the other, so they are not
+    /// diffed. Only a few unchanged lines are kept around each change, and values that differ in
+    /// too many lines are truncated instead of diffed, so that large values such as pixel buffers
+    /// stay readable.
+    pub fn diff(&self) -> Option<Vec<DiffLine>> {
+        match (&self.expected, &self.actual) {
+            (Some(expected), Some(actual)) if expected.contains('\n') || actual.contains('\n') => {
+                Some(diff_lines(expected, actual))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The message, followed by the details and the expected and actual values on their own lines.
+///
+/// Multi-line values are shown as a diff, with expected lines prefixed by `-` and actual lines
+/// prefixed by `+`.
+impl Display for TestFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+
+        if let Some(details) = &self.details {
+            write!(f, "\n{}", details)?;
+        }
+
+        if let Some(diff) = self.diff() {
+            write!(f, "\ndiff (- expected, + actual):")?;
+            for line in diff {
+                match line {
+                    DiffLine::Same(line) => write!(f, "\n  {}", line)?,
+                    DiffLine::Expected(line) => write!(f, "\n- {}", line)?,
+                    DiffLine::Actual(line) => write!(f, "\n+ {}", line)?,
+                    DiffLine::Unchanged(count) => write!(f, "\n  ... {} unchanged lines", count)?,
+                    DiffLine::Omitted(count) => {
+                        write!(f, "\n  ... {} more lines, too many to diff", count)?
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some(expected) = &self.expected {
+            write!(f, "\nexpected: {}", expected)?;
+        }
+        if let Some(actual) = &self.actual {
+            write!(f, "\n  actual: {}", actual)?;
+        }
+
+        Ok(())
+    }
+}
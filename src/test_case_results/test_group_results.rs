@@ -0,0 +1,70 @@
+use crate::{TestCaseResult, TestOutcome};
+
+/// The results of the test cases in a [`crate::TestGroup`].
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestGroupResults {
+    title: String,
+    description: String,
+    results: Vec<TestCaseResult>,
+    groups: Vec<TestGroupResults>,
+}
+
+impl TestGroupResults {
+    #[allow(missing_docs)]
+    pub fn new(
+        title: String,
+        description: String,
+        results: Vec<TestCaseResult>,
+        groups: Vec<TestGroupResults>,
+    ) -> Self {
+        TestGroupResults {
+            title,
+            description,
+            results,
+            groups,
+        }
+    }
+
+    /// The group's title.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The group's description.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The results of the test cases that belong directly to this group.
+    pub fn results(&self) -> &Vec<TestCaseResult> {
+        &self.results
+    }
+
+    /// The results of the groups nested within this group.
+    pub fn groups(&self) -> &Vec<TestGroupResults> {
+        &self.groups
+    }
+
+    /// The results of every test case in this group, including those in nested groups.
+    pub fn all_results(&self) -> Box<dyn Iterator<Item = &TestCaseResult> + '_> {
+        Box::new(
+            self.results
+                .iter()
+                .chain(self.groups.iter().flat_map(|group| group.all_results())),
+        )
+    }
+
+    /// Whether or not all of the test cases in this group and its nested groups had an
+    /// acceptable outcome.
+    pub fn did_pass(&self) -> bool {
+        self.all_results().all(|r| r.did_pass())
+    }
+
+    /// The number of test cases in this group and its nested groups that had the given outcome.
+    pub fn outcome_count(&self, outcome: TestOutcome) -> usize {
+        self.all_results()
+            .filter(|r| r.outcome() == outcome)
+            .count()
+    }
+}
@@ -0,0 +1,61 @@
+/// The outcome of running a test case.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TestOutcome {
+    /// The test case passed.
+    Passed,
+    /// The test case failed.
+    Failed,
+    /// The test case could not be run, such as when the type being tested does not support the
+    /// feature that the test case is testing.
+    Skipped,
+    /// The test case was deliberately not run.
+    Ignored,
+    /// The test case is known to be broken and failed as expected.
+    ExpectedFailure,
+    /// The test case is known to be broken but passed, so it might have been fixed.
+    UnexpectedPass,
+}
+
+impl TestOutcome {
+    /// All of the outcomes, in the order that they are summarized by the visualizers.
+    pub const ALL: [TestOutcome; 6] = [
+        TestOutcome::Passed,
+        TestOutcome::Failed,
+        TestOutcome::Skipped,
+        TestOutcome::Ignored,
+        TestOutcome::ExpectedFailure,
+        TestOutcome::UnexpectedPass,
+    ];
+
+    /// Whether or not the outcome allows the test suite to pass.
+    ///
+    /// Only [`TestOutcome::Failed`] is unacceptable. An [`TestOutcome::UnexpectedPass`] is
+    /// noteworthy, but does not fail the test suite.
+    pub fn is_acceptable(&self) -> bool {
+        !matches!(self, TestOutcome::Failed)
+    }
+
+    /// The outcome of a test case that was expected to fail.
+    ///
+    /// [`TestOutcome::Failed`] becomes [`TestOutcome::ExpectedFailure`] and
+    /// [`TestOutcome::Passed`] becomes [`TestOutcome::UnexpectedPass`].
+    pub fn expecting_failure(self) -> Self {
+        match self {
+            TestOutcome::Passed => TestOutcome::UnexpectedPass,
+            TestOutcome::Failed => TestOutcome::ExpectedFailure,
+            other => other,
+        }
+    }
+}
+
+impl From<bool> for TestOutcome {
+    /// `true` is [`TestOutcome::Passed`] and `false` is [`TestOutcome::Failed`].
+    fn from(did_pass: bool) -> Self {
+        if did_pass {
+            TestOutcome::Passed
+        } else {
+            TestOutcome::Failed
+        }
+    }
+}
@@ -0,0 +1,55 @@
+/// HTML that reports include as-is, without escaping it, such as a test case's visual.
+///
+/// Everything else that reports show, such as titles and failure messages, is escaped so that it
+/// is shown as text. Only create trusted HTML from markup that you control, and use
+/// [`TrustedHtml::from_text`] for text that came from elsewhere.
+///
+/// ```
+/// # use conformer::TrustedHtml;
+/// let visual = TrustedHtml::new(r#"<img src="quads.png" />"#.to_string());
+/// let caption = TrustedHtml::from_text("Render <canvas> quads");
+///
+/// assert_eq!(caption.as_str(), "Render &lt;canvas&gt; quads");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct TrustedHtml(String);
+
+impl TrustedHtml {
+    /// Trust the HTML, so that it is included in reports without being escaped.
+    pub fn new(html: String) -> Self {
+        TrustedHtml(html)
+    }
+
+    /// Escape the text so that it is shown as-is, rather than interpreted as HTML.
+    pub fn from_text(text: &str) -> Self {
+        TrustedHtml(escape_html(text))
+    }
+
+    /// The HTML.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Escape the text so that it is shown as-is when included in HTML, including within
+/// double-quoted attribute values.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,59 @@
+/// Selects test cases by their [`crate::TestCase::title`].
+#[derive(Debug, Clone)]
+pub enum TestFilter {
+    /// Matches test cases whose titles contain the string.
+    Substring(String),
+    /// Matches test cases whose titles are exactly the string.
+    Exact(String),
+    /// Matches test cases whose titles match the regular expression.
+    ///
+    /// Requires the `regex` feature. See [`crate::TestArgs`] for creating one from the command
+    /// line or the environment.
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl TestFilter {
+    /// Whether or not the test case title matches the filter.
+    pub fn matches(&self, title: &str) -> bool {
+        match self {
+            TestFilter::Substring(substring) => title.contains(substring.as_str()),
+            TestFilter::Exact(exact) => title == exact,
+            #[cfg(feature = "regex")]
+            TestFilter::Regex(regex) => regex.is_match(title),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a substring filter matches titles that contain the substring.
+    #[test]
+    fn substring() {
+        let filter = TestFilter::Substring("quad".to_string());
+
+        assert!(filter.matches("Render textured quads"));
+        assert!(!filter.matches("Render particles"));
+    }
+
+    /// Verify that an exact filter only matches the exact title.
+    #[test]
+    fn exact() {
+        let filter = TestFilter::Exact("Render quads".to_string());
+
+        assert!(filter.matches("Render quads"));
+        assert!(!filter.matches("Render quads twice"));
+    }
+
+    /// Verify that a regex filter matches titles that match the regular expression.
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex() {
+        let filter = TestFilter::Regex(regex::Regex::new("^Render (quads|disks)$").unwrap());
+
+        assert!(filter.matches("Render disks"));
+        assert!(!filter.matches("Render particles"));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{TestCase, TestSuite};
+
+pub use self::test_matrix_results::*;
+
+mod test_matrix_results;
+
+/// A test suite that is run against several implementations of the type being tested, such as
+/// each backend of a renderer.
+///
+/// Test cases are consumed when they are run, so the suite is created once per implementation.
+///
+/// ```
+/// # use conformer::{TestMatrix, TestSuite, TestCase};
+/// # struct MetalRenderer;
+/// # struct CpuRenderer;
+/// # trait Renderer {}
+/// # impl Renderer for MetalRenderer {}
+/// # impl Renderer for CpuRenderer {}
+/// fn create_suite() -> TestSuite<Box<dyn Renderer>> {
+///     TestSuite::new("Renderer".to_string(), "".to_string(), vec![])
+/// }
+///
+/// let matrix = TestMatrix::new(create_suite)
+///     .with_implementation("Metal", |_| Box::new(MetalRenderer) as Box<dyn Renderer>)
+///     .with_implementation("CPU", |_| Box::new(CpuRenderer) as Box<dyn Renderer>);
+///
+/// assert_eq!(matrix.implementation_names(), vec!["Metal", "CPU"]);
+/// ```
+pub struct TestMatrix<'a, TypeToTest, Case: ?Sized = dyn TestCase<TypeToTest>> {
+    suite_creator: Box<dyn Fn() -> TestSuite<TypeToTest, Case> + 'a>,
+    implementations: Vec<Implementation<'a, TypeToTest, Case>>,
+}
+
+/// A named implementation of the type being tested, along with the function that creates it for
+/// each test case.
+pub(crate) struct Implementation<'a, TypeToTest, Case: ?Sized> {
+    pub(crate) name: String,
+    pub(crate) type_to_test_creator: TypeToTestCreator<'a, TypeToTest, Case>,
+}
+
+type TypeToTestCreator<'a, TypeToTest, Case> = Box<dyn Fn(&Box<Case>) -> TypeToTest + 'a>;
+
+impl<'a, TypeToTest, Case: ?Sized + TestCase<TypeToTest>> TestMatrix<'a, TypeToTest, Case> {
+    /// Create a matrix that runs the suites that the suite creator creates.
+    pub fn new(suite_creator: impl Fn() -> TestSuite<TypeToTest, Case> + 'a) -> Self {
+        TestMatrix {
+            suite_creator: Box::new(suite_creator),
+            implementations: vec![],
+        }
+    }
+
+    /// Add an implementation to run every test case against, in the same way that a
+    /// `type_to_test_creator` is passed to [`crate::TestRunner::run`].
+    ///
+    /// Implementations are shown in the order that they were added.
+    pub fn with_implementation(
+        mut self,
+        name: &str,
+        type_to_test_creator: impl Fn(&Box<Case>) -> TypeToTest + 'a,
+    ) -> Self {
+        self.implementations.push(Implementation {
+            name: name.to_string(),
+            type_to_test_creator: Box::new(type_to_test_creator),
+        });
+        self
+    }
+
+    /// The names of the implementations, in the order that they were added.
+    pub fn implementation_names(&self) -> Vec<&str> {
+        self.implementations
+            .iter()
+            .map(|implementation| implementation.name.as_str())
+            .collect()
+    }
+
+    /// Create a new suite to run against one of the implementations.
+    pub(crate) fn create_suite(&self) -> TestSuite<TypeToTest, Case> {
+        (self.suite_creator)()
+    }
+
+    pub(crate) fn implementations(&self) -> &[Implementation<'a, TypeToTest, Case>] {
+        &self.implementations
+    }
+}
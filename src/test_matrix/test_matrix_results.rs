@@ -0,0 +1,244 @@
+use crate::test_case_results::ResultKey;
+use crate::{TestCaseResult, TestCaseResults};
+use std::collections::HashMap;
+
+/// The results of running a test suite against each implementation in a [`crate::TestMatrix`].
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestMatrixResults {
+    suite_title: String,
+    suite_description: String,
+    implementations: Vec<ImplementationResults>,
+}
+
+/// The results of running a test suite against one implementation.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImplementationResults {
+    name: String,
+    results: TestCaseResults,
+}
+
+/// The results of one test case for each implementation, in the same order as
+/// [`TestMatrixResults::implementations`].
+///
+/// A result is `None` if the test case was not run against that implementation, such as when
+/// the implementation's suite did not contain it.
+#[derive(Debug)]
+pub struct TestMatrixRow<'a> {
+    key: ResultKey<'a>,
+    results: Vec<Option<&'a TestCaseResult>>,
+}
+
+impl TestMatrixResults {
+    /// # Panics
+    ///
+    /// Panics if there are no implementations.
+    pub fn new(
+        suite_title: String,
+        suite_description: String,
+        implementations: Vec<ImplementationResults>,
+    ) -> Self {
+        assert!(
+            !implementations.is_empty(),
+            r#"The "{}" test matrix did not have any implementations."#,
+            suite_title
+        );
+
+        TestMatrixResults {
+            suite_title,
+            suite_description,
+            implementations,
+        }
+    }
+
+    /// The test suite's title.
+    pub fn suite_title(&self) -> &String {
+        &self.suite_title
+    }
+
+    /// The test suite's description.
+    pub fn suite_description(&self) -> &String {
+        &self.suite_description
+    }
+
+    /// The results of each implementation, in the order that the implementations were added.
+    pub fn implementations(&self) -> &[ImplementationResults] {
+        &self.implementations
+    }
+
+    /// The results of the implementation with the given name.
+    pub fn implementation(&self, name: &str) -> Option<&TestCaseResults> {
+        self.implementations
+            .iter()
+            .find(|implementation| implementation.name == name)
+            .map(|implementation| &implementation.results)
+    }
+
+    /// One row per test case, matched across implementations by title and the titles of the
+    /// groups that it is in, in the order that the test cases were first seen.
+    pub fn rows(&self) -> Vec<TestMatrixRow<'_>> {
+        let implementation_count = self.implementations.len();
+        let mut rows: Vec<TestMatrixRow<'_>> = vec![];
+        let mut row_indices: HashMap<ResultKey<'_>, usize> = HashMap::new();
+
+        for (idx, implementation) in self.implementations.iter().enumerate() {
+            for (key, result) in implementation.results.keyed_results() {
+                let row_idx = *row_indices.entry(key.clone()).or_insert_with(|| {
+                    rows.push(TestMatrixRow {
+                        key,
+                        results: vec![None; implementation_count],
+                    });
+                    rows.len() - 1
+                });
+                rows[row_idx].results[idx] = Some(result);
+            }
+        }
+
+        rows
+    }
+
+    /// Whether or not every implementation passed.
+    ///
+    /// See [`TestCaseResults::did_pass`].
+    pub fn did_pass(&self) -> bool {
+        self.implementations
+            .iter()
+            .all(|implementation| implementation.results.did_pass())
+    }
+
+    /// # Panics
+    ///
+    /// Panics if one or more test cases failed against any implementation.
+    pub fn assert_did_pass(&self) {
+        assert!(self.did_pass());
+    }
+}
+
+impl ImplementationResults {
+    #[allow(missing_docs)]
+    pub fn new(name: String, results: TestCaseResults) -> Self {
+        ImplementationResults { name, results }
+    }
+
+    /// The implementation's name, such as "Metal".
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The results of running the test suite against the implementation.
+    pub fn results(&self) -> &TestCaseResults {
+        &self.results
+    }
+}
+
+impl<'a> TestMatrixRow<'a> {
+    /// The test case's title.
+    pub fn title(&self) -> &'a str {
+        self.key.title
+    }
+
+    /// The titles of the groups that the test case is in followed by its title, such as
+    /// "Chapter 1 / Render quads".
+    pub fn path(&self) -> String {
+        self.key.path()
+    }
+
+    /// The test case's result for each implementation.
+    pub fn results(&self) -> &[Option<&'a TestCaseResult>] {
+        &self.results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TestGroupResults, TestOutcome};
+
+    /// Verify that test cases are matched by title across implementations.
+    #[test]
+    fn rows() {
+        let result =
+            |title: &str, outcome| TestCaseResult::new(title.to_string(), "".to_string(), outcome);
+        let suite = |results| TestCaseResults::new("Suite".to_string(), "".to_string(), results);
+
+        let matrix = TestMatrixResults::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![
+                ImplementationResults::new(
+                    "Metal".to_string(),
+                    suite(vec![result("Quads", TestOutcome::Passed)]),
+                ),
+                ImplementationResults::new(
+                    "CPU".to_string(),
+                    suite(vec![
+                        result("Disks", TestOutcome::Passed),
+                        result("Quads", TestOutcome::Failed),
+                    ]),
+                ),
+            ],
+        );
+
+        let rows: Vec<(&str, Vec<Option<TestOutcome>>)> = matrix
+            .rows()
+            .iter()
+            .map(|row| {
+                (
+                    row.title(),
+                    row.results()
+                        .iter()
+                        .map(|result| result.map(|r| r.outcome()))
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    "Quads",
+                    vec![Some(TestOutcome::Passed), Some(TestOutcome::Failed)]
+                ),
+                ("Disks", vec![None, Some(TestOutcome::Passed)]),
+            ]
+        );
+        assert!(!matrix.did_pass());
+    }
+
+    /// Verify that test cases that share a title but are in different groups get their own rows.
+    #[test]
+    fn duplicate_titles_in_groups() {
+        let suite = |outcome| {
+            let result = || TestCaseResult::new("Quads".to_string(), "".to_string(), outcome);
+            let group = |title: &str| {
+                TestGroupResults::new(title.to_string(), "".to_string(), vec![result()], vec![])
+            };
+            TestCaseResults::with_groups(
+                "Suite".to_string(),
+                "".to_string(),
+                vec![],
+                vec![group("2D"), group("3D")],
+            )
+        };
+
+        let matrix = TestMatrixResults::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![
+                ImplementationResults::new("Metal".to_string(), suite(TestOutcome::Passed)),
+                ImplementationResults::new("CPU".to_string(), suite(TestOutcome::Failed)),
+            ],
+        );
+
+        let rows: Vec<(String, usize)> = matrix
+            .rows()
+            .iter()
+            .map(|row| (row.path(), row.results().iter().flatten().count()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![("2D / Quads".to_string(), 2), ("3D / Quads".to_string(), 2)]
+        );
+    }
+}
@@ -0,0 +1,1217 @@
+use crate::{
+    catch_test_case_panic, ImplementationResults, TestCase, TestCaseDuration, TestCasePanic,
+    TestCaseResult, TestCaseResults, TestGroup, TestGroupResults, TestMatrix, TestMatrixResults,
+    TestSuite, TestSuiteInfo, Visualizer,
+};
+use std::fmt::{Debug, Formatter};
+use std::num::NonZeroUsize;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Runs test suites, either one test case at a time or in parallel.
+///
+/// ```
+/// # use conformer::TestRunner;
+/// # use std::num::NonZeroUsize;
+/// let runner = TestRunner::new().with_worker_count(NonZeroUsize::new(4).unwrap());
+/// ```
+pub struct TestRunner {
+    worker_count: NonZeroUsize,
+    visualizers: Vec<Box<dyn Visualizer>>,
+}
+
+impl TestRunner {
+    /// Create a test runner that uses one worker per available CPU when running in parallel.
+    pub fn new() -> Self {
+        TestRunner {
+            worker_count: std::thread::available_parallelism()
+                .unwrap_or(NonZeroUsize::new(1).unwrap()),
+            visualizers: vec![],
+        }
+    }
+
+    /// Set the number of threads that [`TestRunner::run_parallel`] runs test cases on.
+    pub fn with_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// The number of threads that [`TestRunner::run_parallel`] runs test cases on.
+    pub fn worker_count(&self) -> NonZeroUsize {
+        self.worker_count
+    }
+
+    /// Attach a visualizer that is told when a suite starts, as each of its test cases finishes
+    /// and when the suite finishes.
+    ///
+    /// Any number of visualizers can be attached, and they are told about each event in the
+    /// order that they were attached.
+    pub fn with_visualizer(mut self, visualizer: impl Visualizer + 'static) -> Self {
+        self.visualizers.push(Box::new(visualizer));
+        self
+    }
+
+    /// Run every test case in the suite, one after the other, on the current thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the suite does not have any test cases, such as when a [`crate::TestFilter`]
+    /// did not match any of them.
+    ///
+    /// Each test case is run in isolation, so a test case that panics is recorded as a failed
+    /// [`TestCaseResult`] with its [`crate::TestCasePanic`] and the remaining test cases still
+    /// get run.
+    ///
+    /// Since the test cases run on the current thread they cannot be interrupted, so a test case
+    /// whose setup and run together exceed its timeout is only recorded as timed out after it
+    /// finishes, and one that never finishes (such as one that deadlocks) hangs the suite. A
+    /// warning is printed to stderr if any test case has a timeout. Use
+    /// [`TestRunner::run_with_timeouts`] or [`TestRunner::run_parallel`] to stop waiting on test
+    /// cases that never finish.
+    pub fn run<TypeToTest, Case, TypeToTestCreator>(
+        &mut self,
+        type_to_test_creator: TypeToTestCreator,
+        suite: TestSuite<TypeToTest, Case>,
+    ) -> TestCaseResults
+    where
+        Case: ?Sized + TestCase<TypeToTest>,
+        TypeToTestCreator: Fn(&Box<Case>) -> TypeToTest,
+    {
+        assert_has_test_cases(&suite);
+
+        let suite_start = Instant::now();
+        let suite_timeout = suite.timeout;
+        let (layout, test_cases) = SuiteLayout::flatten(suite);
+        if test_cases
+            .iter()
+            .any(|test_case| test_case.timeout().or(suite_timeout).is_some())
+        {
+            eprintln!(
+                r#"warning: the "{}" test suite has timeouts, which TestRunner::run cannot enforce. Use TestRunner::run_with_timeouts to stop waiting on test cases that never finish."#,
+                layout.title
+            );
+        }
+        self.suite_started(&layout.suite_info(test_cases.len()));
+
+        let results = test_cases
+            .into_iter()
+            .map(|test_case| {
+                let info = TestCaseInfo::new(test_case.as_ref(), suite_timeout);
+                self.test_case_started(&info.title);
+
+                let (result, duration) = run_test_case(&info, test_case, &type_to_test_creator);
+
+                let result = match info.timeout {
+                    Some(timeout) if duration.total() > timeout => info.timed_out(timeout),
+                    _ => result,
+                };
+                let title = info.title.clone();
+                let result = info.finish(result, duration);
+
+                self.test_case_finished(&title, &result);
+                result
+            })
+            .collect();
+
+        let mut results = layout.into_results(results);
+        results.set_duration(suite_start.elapsed());
+        self.suite_finished(results)
+    }
+
+    /// Run the test cases in the suite across [`TestRunner::worker_count`] threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the suite does not have any test cases, such as when a [`crate::TestFilter`]
+    /// did not match any of them.
+    ///
+    /// The returned results are in the same order and groups as the suite's test cases,
+    /// regardless of the order in which the test cases finished.
+    ///
+    /// A test case that has a timeout is run on its own thread, along with creating the type that
+    /// it tests. If the setup and run together do not finish in time it is recorded as timed out
+    /// and left running in the background, so that a test case that never finishes (such as one
+    /// that deadlocks) does not prevent the suite from finishing.
+    pub fn run_parallel<TypeToTest, Case, TypeToTestCreator>(
+        &mut self,
+        type_to_test_creator: TypeToTestCreator,
+        suite: TestSuite<TypeToTest, Case>,
+    ) -> TestCaseResults
+    where
+        TypeToTest: 'static,
+        Case: ?Sized + TestCase<TypeToTest> + Send + 'static,
+        TypeToTestCreator: Fn(&Box<Case>) -> TypeToTest + Send + Sync + 'static,
+    {
+        self.run_on_workers(self.worker_count, type_to_test_creator, suite)
+    }
+
+    /// Run every test case in the suite, one after the other, stopping waiting on any test case
+    /// that does not finish within its timeout.
+    ///
+    /// Unlike [`TestRunner::run`], each test case that has a timeout is run on its own thread
+    /// along with creating the type that it tests, as in [`TestRunner::run_parallel`], so a test
+    /// case that never finishes (such as one that deadlocks) is recorded as timed out and left
+    /// running in the background instead of hanging the suite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the suite does not have any test cases, such as when a [`crate::TestFilter`]
+    /// did not match any of them.
+    pub fn run_with_timeouts<TypeToTest, Case, TypeToTestCreator>(
+        &mut self,
+        type_to_test_creator: TypeToTestCreator,
+        suite: TestSuite<TypeToTest, Case>,
+    ) -> TestCaseResults
+    where
+        TypeToTest: 'static,
+        Case: ?Sized + TestCase<TypeToTest> + Send + 'static,
+        TypeToTestCreator: Fn(&Box<Case>) -> TypeToTest + Send + Sync + 'static,
+    {
+        self.run_on_workers(NonZeroUsize::new(1).unwrap(), type_to_test_creator, suite)
+    }
+
+    fn run_on_workers<TypeToTest, Case, TypeToTestCreator>(
+        &mut self,
+        worker_count: NonZeroUsize,
+        type_to_test_creator: TypeToTestCreator,
+        suite: TestSuite<TypeToTest, Case>,
+    ) -> TestCaseResults
+    where
+        TypeToTest: 'static,
+        Case: ?Sized + TestCase<TypeToTest> + Send + 'static,
+        TypeToTestCreator: Fn(&Box<Case>) -> TypeToTest + Send + Sync + 'static,
+    {
+        assert_has_test_cases(&suite);
+
+        let suite_start = Instant::now();
+        let suite_timeout = suite.timeout;
+        let (layout, test_cases) = SuiteLayout::flatten(suite);
+        let test_case_count = test_cases.len();
+        self.suite_started(&layout.suite_info(test_case_count));
+        let worker_count = worker_count.get().min(test_case_count);
+
+        let test_cases = Mutex::new(test_cases.into_iter().enumerate());
+        let type_to_test_creator = Arc::new(type_to_test_creator);
+        let (event_sender, event_receiver) = channel();
+        let mut results: Vec<Option<TestCaseResult>> = (0..test_case_count).map(|_| None).collect();
+
+        std::thread::scope(|scope| {
+            for _ in 0..worker_count {
+                let test_cases = &test_cases;
+                let type_to_test_creator = &type_to_test_creator;
+                let event_sender = event_sender.clone();
+
+                scope.spawn(move || loop {
+                    let next = test_cases.lock().unwrap().next();
+                    let (idx, test_case) = match next {
+                        Some(next) => next,
+                        None => break,
+                    };
+
+                    let info = TestCaseInfo::new(test_case.as_ref(), suite_timeout);
+                    event_sender
+                        .send(RunnerEvent::Started(info.title.clone()))
+                        .unwrap();
+
+                    let (result, duration) = match info.timeout {
+                        Some(timeout) => run_test_case_with_timeout(
+                            &info,
+                            test_case,
+                            Arc::clone(type_to_test_creator),
+                            timeout,
+                        ),
+                        None => run_test_case(&info, test_case, type_to_test_creator.as_ref()),
+                    };
+                    let title = info.title.clone();
+                    let result = info.finish(result, duration);
+                    event_sender
+                        .send(RunnerEvent::Finished(idx, title, Box::new(result)))
+                        .unwrap();
+                });
+            }
+            drop(event_sender);
+
+            for event in event_receiver {
+                match event {
+                    RunnerEvent::Started(title) => self.test_case_started(&title),
+                    RunnerEvent::Finished(idx, title, result) => {
+                        self.test_case_finished(&title, &result);
+                        results[idx] = Some(*result);
+                    }
+                }
+            }
+        });
+        let results = results.into_iter().map(Option::unwrap).collect();
+
+        let mut results = layout.into_results(results);
+        results.set_duration(suite_start.elapsed());
+        self.suite_finished(results)
+    }
+
+    fn suite_started(&mut self, suite: &TestSuiteInfo) {
+        for visualizer in self.visualizers.iter_mut() {
+            visualizer.suite_started(suite);
+        }
+    }
+
+    /// Run every test case in the matrix's suite against each of its implementations, one
+    /// implementation after the other, using [`TestRunner::run`].
+    ///
+    /// Attached visualizers are told about each implementation's suite separately. Each suite's
+    /// title has the implementation's name appended, such as "Renderer (Metal)", so that their
+    /// results can be told apart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix does not have any implementations, or if any of its suites do not have
+    /// any test cases.
+    pub fn run_matrix<TypeToTest, Case>(
+        &mut self,
+        matrix: TestMatrix<'_, TypeToTest, Case>,
+    ) -> TestMatrixResults
+    where
+        Case: ?Sized + TestCase<TypeToTest>,
+    {
+        let suite = matrix.create_suite();
+        let (suite_title, suite_description) = (suite.title.clone(), suite.description.clone());
+
+        let mut suite = Some(suite);
+        let mut implementations = vec![];
+        for implementation in matrix.implementations() {
+            let mut suite = suite.take().unwrap_or_else(|| matrix.create_suite());
+            suite.title = format!("{} ({})", suite.title, implementation.name);
+
+            let results = self.run(&implementation.type_to_test_creator, suite);
+            implementations.push(ImplementationResults::new(
+                implementation.name.clone(),
+                results,
+            ));
+        }
+
+        TestMatrixResults::new(suite_title, suite_description, implementations)
+    }
+
+    fn test_case_started(&mut self, title: &str) {
+        for visualizer in self.visualizers.iter_mut() {
+            visualizer.test_case_started(title);
+        }
+    }
+
+    fn test_case_finished(&mut self, title: &str, result: &TestCaseResult) {
+        for visualizer in self.visualizers.iter_mut() {
+            visualizer.test_case_finished(title, result);
+        }
+    }
+
+    fn suite_finished(&mut self, results: TestCaseResults) -> TestCaseResults {
+        for visualizer in self.visualizers.iter_mut() {
+            visualizer.suite_finished(&results);
+        }
+
+        results
+    }
+}
+
+impl Debug for TestRunner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestRunner")
+            .field("worker_count", &self.worker_count)
+            .field("visualizer_count", &self.visualizers.len())
+            .finish()
+    }
+}
+
+impl Default for TestRunner {
+    fn default() -> Self {
+        TestRunner::new()
+    }
+}
+
+fn run_test_case<TypeToTest, Case, TypeToTestCreator>(
+    info: &TestCaseInfo,
+    test_case: Box<Case>,
+    type_to_test_creator: &TypeToTestCreator,
+) -> (TestCaseResult, TestCaseDuration)
+where
+    Case: ?Sized + TestCase<TypeToTest>,
+    TypeToTestCreator: Fn(&Box<Case>) -> TypeToTest,
+{
+    let start = Instant::now();
+    let mut setup = None;
+
+    let test_case_result = catch_test_case_panic(|| {
+        let type_to_test = type_to_test_creator(&test_case);
+        setup = Some(start.elapsed());
+        test_case.run(type_to_test)
+    });
+
+    let elapsed = start.elapsed();
+    let setup = setup.unwrap_or(elapsed);
+    let duration = TestCaseDuration::new(setup, elapsed - setup);
+
+    let result = match test_case_result {
+        Ok(test_case_result) => test_case_result,
+        Err(panic) => info.panicked(panic),
+    };
+    (result, duration)
+}
+
+fn assert_has_test_cases<TypeToTest, Case: ?Sized + TestCase<TypeToTest>>(
+    suite: &TestSuite<TypeToTest, Case>,
+) {
+    if suite.test_case_count() > 0 {
+        return;
+    }
+
+    if suite.filtered_out_count() > 0 {
+        panic!(
+            r#"The filter did not match any of the {} test cases in the "{}" test suite."#,
+            suite.filtered_out_count(),
+            suite.title
+        );
+    } else {
+        panic!(
+            r#"The "{}" test suite does not have any test cases."#,
+            suite.title
+        );
+    }
+}
+
+/// Create the type to test and run the test case on their own thread, giving up on them if they
+/// do not finish within the timeout.
+fn run_test_case_with_timeout<TypeToTest, Case, TypeToTestCreator>(
+    info: &TestCaseInfo,
+    test_case: Box<Case>,
+    type_to_test_creator: Arc<TypeToTestCreator>,
+    timeout: Duration,
+) -> (TestCaseResult, TestCaseDuration)
+where
+    TypeToTest: 'static,
+    Case: ?Sized + TestCase<TypeToTest> + Send + 'static,
+    TypeToTestCreator: Fn(&Box<Case>) -> TypeToTest + Send + Sync + 'static,
+{
+    let start = Instant::now();
+    let (setup_sender, setup_receiver) = channel();
+    let (result_sender, result_receiver) = channel();
+    std::thread::spawn(move || {
+        let result = catch_test_case_panic(|| {
+            let type_to_test = type_to_test_creator(&test_case);
+            let _ = setup_sender.send(start.elapsed());
+            test_case.run(type_to_test)
+        });
+        let _ = result_sender.send(result);
+    });
+
+    let result = match result_receiver.recv_timeout(timeout) {
+        Ok(Ok(test_case_result)) => test_case_result,
+        Ok(Err(panic)) => info.panicked(panic),
+        Err(RecvTimeoutError::Timeout) => info.timed_out(timeout),
+        Err(RecvTimeoutError::Disconnected) => info.panicked(TestCasePanic::new(
+            "test case thread exited without a result".to_string(),
+            None,
+        )),
+    };
+
+    let elapsed = start.elapsed();
+    let setup = setup_receiver.try_recv().unwrap_or(elapsed);
+    (result, TestCaseDuration::new(setup, elapsed - setup))
+}
+
+/// Sent from the threads that run test cases in parallel, so that visualizers can be told about
+/// test cases as they start and finish.
+enum RunnerEvent {
+    Started(String),
+    Finished(usize, String, Box<TestCaseResult>),
+}
+
+/// The titles and descriptions of a test suite and its groups, along with the number of test
+/// cases in each.
+///
+/// Test cases are run from a flat list, and the layout is used to put their results back into
+/// the groups that they came from.
+struct SuiteLayout {
+    title: String,
+    description: String,
+    test_case_count: usize,
+    groups: Vec<GroupLayout>,
+    filtered_out_count: usize,
+}
+
+struct GroupLayout {
+    title: String,
+    description: String,
+    test_case_count: usize,
+    groups: Vec<GroupLayout>,
+}
+
+impl SuiteLayout {
+    /// Split the suite into its layout and its test cases. A group's own test cases come before
+    /// the test cases of its nested groups.
+    fn flatten<TypeToTest, Case>(suite: TestSuite<TypeToTest, Case>) -> (Self, Vec<Box<Case>>)
+    where
+        Case: ?Sized + TestCase<TypeToTest>,
+    {
+        let filtered_out_count = suite.filtered_out_count();
+
+        let mut test_cases = vec![];
+        let test_case_count = suite.test_cases.len();
+        test_cases.extend(suite.test_cases);
+
+        let groups = suite
+            .groups
+            .into_iter()
+            .map(|group| GroupLayout::flatten(group, &mut test_cases))
+            .collect();
+
+        let layout = SuiteLayout {
+            title: suite.title,
+            description: suite.description,
+            test_case_count,
+            groups,
+            filtered_out_count,
+        };
+
+        (layout, test_cases)
+    }
+
+    fn suite_info(&self, test_case_count: usize) -> TestSuiteInfo {
+        TestSuiteInfo::new(
+            self.title.clone(),
+            self.description.clone(),
+            test_case_count,
+            self.filtered_out_count,
+        )
+    }
+
+    /// Put the results of the flattened test cases back into their groups.
+    fn into_results(self, results: Vec<TestCaseResult>) -> TestCaseResults {
+        let mut results = results.into_iter();
+
+        let suite_results = results.by_ref().take(self.test_case_count).collect();
+        let groups = self
+            .groups
+            .into_iter()
+            .map(|group| group.into_results(&mut results))
+            .collect();
+
+        let mut results =
+            TestCaseResults::with_groups(self.title, self.description, suite_results, groups);
+        results.set_filtered_out_count(self.filtered_out_count);
+        results
+    }
+}
+
+impl GroupLayout {
+    fn flatten<TypeToTest, Case>(
+        group: TestGroup<TypeToTest, Case>,
+        test_cases: &mut Vec<Box<Case>>,
+    ) -> Self
+    where
+        Case: ?Sized + TestCase<TypeToTest>,
+    {
+        let test_case_count = group.test_cases.len();
+        test_cases.extend(group.test_cases);
+
+        let groups = group
+            .groups
+            .into_iter()
+            .map(|group| GroupLayout::flatten(group, test_cases))
+            .collect();
+
+        GroupLayout {
+            title: group.title,
+            description: group.description,
+            test_case_count,
+            groups,
+        }
+    }
+
+    fn into_results(self, results: &mut impl Iterator<Item = TestCaseResult>) -> TestGroupResults {
+        let group_results = results.by_ref().take(self.test_case_count).collect();
+        let groups = self
+            .groups
+            .into_iter()
+            .map(|group| group.into_results(results))
+            .collect();
+
+        TestGroupResults::new(self.title, self.description, group_results, groups)
+    }
+}
+
+/// Information about a test case that is captured before it is run, so that a result can be
+/// created for a test case that did not produce its own [`TestCaseResult`].
+struct TestCaseInfo {
+    title: String,
+    description: String,
+    tags: Vec<String>,
+    expect_failure: bool,
+    timeout: Option<Duration>,
+}
+
+impl TestCaseInfo {
+    fn new<TypeToTest, Case>(test_case: &Case, suite_timeout: Option<Duration>) -> Self
+    where
+        Case: ?Sized + TestCase<TypeToTest>,
+    {
+        TestCaseInfo {
+            title: test_case.title().to_string(),
+            description: test_case.description().to_string(),
+            tags: test_case.tags().to_vec(),
+            expect_failure: test_case.expect_failure(),
+            timeout: test_case.timeout().or(suite_timeout),
+        }
+    }
+
+    fn panicked(&self, panic: TestCasePanic) -> TestCaseResult {
+        TestCaseResult::panicked(self.title.clone(), self.description.clone(), panic)
+    }
+
+    fn timed_out(&self, timeout: Duration) -> TestCaseResult {
+        TestCaseResult::timed_out(self.title.clone(), self.description.clone(), timeout)
+    }
+
+    /// Apply what we know about the test case to its result.
+    ///
+    /// The result gets the test case's tags and duration, and the outcome of a test case that is
+    /// expected to fail is converted using [`crate::TestOutcome::expecting_failure`].
+    fn finish(self, mut result: TestCaseResult, duration: TestCaseDuration) -> TestCaseResult {
+        result.set_tags(self.tags);
+        result.set_duration(duration);
+
+        if self.expect_failure {
+            result.set_outcome(result.outcome().expecting_failure());
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TagFilter, TestFilter, TestGroup, TestOutcome};
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+    use std::rc::Rc;
+    use std::thread::ThreadId;
+    use std::time::Duration;
+
+    /// Verify that a test case that panics does not prevent the other test cases from running.
+    #[test]
+    fn panicking_test_case_does_not_abort_suite() {
+        let suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![
+                Box::new(PanickingTestCase) as Box<dyn TestCase<()>>,
+                Box::new(SleepingTestCase::new(0)),
+            ],
+        );
+
+        let results = TestRunner::new().run(|_| (), suite);
+
+        assert_eq!(results.results().len(), 2);
+
+        let panicked = &results.results()[0];
+        assert!(!panicked.did_pass());
+        assert_eq!(panicked.title(), "Panics");
+        assert_eq!(panicked.description(), "Always panics.");
+        let panic = panicked.panic().unwrap();
+        assert_eq!(panic.message(), "oh no");
+        assert!(panic.location().unwrap().starts_with("src/test_runner.rs:"));
+
+        assert!(results.results()[1].did_pass());
+    }
+
+    /// Verify that running test cases in parallel preserves the order of the test cases, even
+    /// when later test cases finish first.
+    #[test]
+    fn parallel_results_are_in_test_case_order() {
+        let thread_ids = Arc::new(Mutex::new(HashSet::new()));
+
+        let test_cases: Vec<Box<dyn TestCase<()> + Send>> = (0..8)
+            .map(|idx| Box::new(SleepingTestCase::new(8 - idx)) as Box<dyn TestCase<()> + Send>)
+            .collect();
+        let suite = TestSuite::new("Suite".to_string(), "".to_string(), test_cases);
+
+        let results = TestRunner::new()
+            .with_worker_count(NonZeroUsize::new(4).unwrap())
+            .run_parallel(
+                {
+                    let thread_ids = Arc::clone(&thread_ids);
+                    move |_| {
+                        thread_ids
+                            .lock()
+                            .unwrap()
+                            .insert(std::thread::current().id());
+                    }
+                },
+                suite,
+            );
+
+        let titles: Vec<&str> = results.results().iter().map(|r| r.title()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Sleep 8", "Sleep 7", "Sleep 6", "Sleep 5", "Sleep 4", "Sleep 3", "Sleep 2",
+                "Sleep 1"
+            ]
+        );
+
+        let thread_ids: HashSet<ThreadId> = thread_ids.lock().unwrap().clone();
+        assert!(thread_ids.len() > 1);
+    }
+
+    /// Verify that a test case that panics while running in parallel is recorded.
+    #[test]
+    fn parallel_panicking_test_case_does_not_abort_suite() {
+        let suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![
+                Box::new(SleepingTestCase::new(0)) as Box<dyn TestCase<()> + Send>,
+                Box::new(PanickingTestCase),
+                Box::new(SleepingTestCase::new(0)),
+            ],
+        );
+
+        let results = TestRunner::new()
+            .with_worker_count(NonZeroUsize::new(2).unwrap())
+            .run_parallel(|_| (), suite);
+
+        assert!(results.results()[0].did_pass());
+        assert!(results.results()[1].panic().is_some());
+        assert!(results.results()[2].did_pass());
+    }
+
+    /// Verify that a test case that never finishes is recorded as timed out without preventing
+    /// the suite from finishing.
+    #[test]
+    fn parallel_test_case_times_out() {
+        let mut suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![
+                Box::new(SleepingTestCase::new(0)) as Box<dyn TestCase<()> + Send>,
+                Box::new(NeverFinishesTestCase),
+            ],
+        );
+        suite.timeout = Some(Duration::from_millis(50));
+
+        let results = TestRunner::new().run_parallel(|_| (), suite);
+
+        assert!(results.results()[0].did_pass());
+        assert!(!results.results()[1].did_pass());
+        assert_eq!(
+            results.results()[1].timeout(),
+            Some(Duration::from_millis(50))
+        );
+    }
+
+    /// Verify that creating the type to test counts towards the timeout, so that setup that never
+    /// finishes is also recorded as timed out.
+    #[test]
+    fn parallel_setup_times_out() {
+        let mut suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![Box::new(SleepingTestCase::new(0)) as Box<dyn TestCase<()> + Send>],
+        );
+        suite.timeout = Some(Duration::from_millis(50));
+
+        let results = TestRunner::new().run_parallel(
+            |_| loop {
+                std::thread::park();
+            },
+            suite,
+        );
+
+        let result = &results.results()[0];
+        assert_eq!(result.timeout(), Some(Duration::from_millis(50)));
+        assert!(result.duration().unwrap().setup() >= Duration::from_millis(50));
+        assert_eq!(result.duration().unwrap().run(), Duration::ZERO);
+    }
+
+    /// Verify that running one test case at a time with timeouts stops waiting on a test case that
+    /// never finishes.
+    #[test]
+    fn run_with_timeouts() {
+        let mut suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![
+                Box::new(NeverFinishesTestCase) as Box<dyn TestCase<()> + Send>,
+                Box::new(SleepingTestCase::new(0)),
+            ],
+        );
+        suite.timeout = Some(Duration::from_millis(50));
+
+        let results = TestRunner::new().run_with_timeouts(|_| (), suite);
+
+        assert_eq!(
+            results.results()[0].timeout(),
+            Some(Duration::from_millis(50))
+        );
+        assert!(results.results()[1].did_pass());
+    }
+
+    /// Verify that a test case's timeout overrides the suite's timeout.
+    #[test]
+    fn test_case_timeout_overrides_suite_timeout() {
+        let mut suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![
+                Box::new(SleepingTestCase::new(3).with_timeout(Duration::from_millis(5)))
+                    as Box<dyn TestCase<()>>,
+            ],
+        );
+        suite.timeout = Some(Duration::from_secs(60));
+
+        let results = TestRunner::new().run(|_| (), suite);
+
+        assert_eq!(
+            results.results()[0].timeout(),
+            Some(Duration::from_millis(5))
+        );
+        assert_eq!(results.results()[0].title(), "Sleep");
+    }
+
+    /// Verify that test cases that are expected to fail do not fail the test suite.
+    #[test]
+    fn expected_failures() {
+        let suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![
+                Box::new(ExpectedToFail(PanickingTestCase)) as Box<dyn TestCase<()>>,
+                Box::new(ExpectedToFail(SleepingTestCase::new(0))),
+            ],
+        );
+
+        let results = TestRunner::new().run(|_| (), suite);
+
+        assert_eq!(results.results()[0].outcome(), TestOutcome::ExpectedFailure);
+        assert!(results.results()[0].panic().is_some());
+        assert_eq!(results.results()[1].outcome(), TestOutcome::UnexpectedPass);
+        assert!(results.did_pass());
+    }
+
+    /// Verify that test cases that do not match the filter are not run, and are counted as
+    /// filtered out.
+    #[test]
+    fn filtered_out_test_cases_are_not_run() {
+        let mut suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![
+                Box::new(SleepingTestCase::new(1)) as Box<dyn TestCase<()>>,
+                Box::new(PanickingTestCase),
+                Box::new(SleepingTestCase::new(2)),
+            ],
+        );
+        suite.apply_filter(&TestFilter::Substring("Sleep".to_string()));
+
+        let results = TestRunner::new().run(|_| (), suite);
+
+        let titles: Vec<&str> = results.results().iter().map(|r| r.title()).collect();
+        assert_eq!(titles, vec!["Sleep 1", "Sleep 2"]);
+        assert_eq!(results.filtered_out_count(), 1);
+    }
+
+    /// Verify that test cases that do not match the tag filter are not run, and that the test
+    /// case's tags are copied to its result.
+    #[test]
+    fn tag_filter() {
+        let mut suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![
+                Box::new(SleepingTestCase::new(1).with_tags(&["ui"])) as Box<dyn TestCase<()>>,
+                Box::new(SleepingTestCase::new(2).with_tags(&["ui", "particles"])),
+                Box::new(SleepingTestCase::new(3).with_tags(&["textures"])),
+            ],
+        );
+        suite.apply_tag_filter(&TagFilter::parse("ui,textures,!particles"));
+
+        let results = TestRunner::new().run(|_| (), suite);
+
+        let titles: Vec<&str> = results.results().iter().map(|r| r.title()).collect();
+        assert_eq!(titles, vec!["Sleep 1", "Sleep 3"]);
+        assert_eq!(results.results()[1].tags(), &["textures".to_string()]);
+        assert_eq!(results.filtered_out_count(), 1);
+    }
+
+    /// Verify that we get a clear error message when every test case is filtered out.
+    #[test]
+    #[should_panic(expected = r#"The filter did not match any of the 1 test cases in the "Suite""#)]
+    fn filter_that_matches_nothing() {
+        let mut suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![Box::new(PanickingTestCase) as Box<dyn TestCase<()>>],
+        );
+        suite.apply_filter(&TestFilter::Exact("Sleep".to_string()));
+
+        TestRunner::new().run(|_| (), suite);
+    }
+
+    /// Verify that the results of test cases in groups are put back into their groups, whether
+    /// the test cases are run sequentially or in parallel.
+    #[test]
+    fn groups_are_preserved() {
+        fn suite() -> TestSuite<(), dyn TestCase<()> + Send> {
+            let mut chapter = TestGroup::new(
+                "Chapter 1".to_string(),
+                "".to_string(),
+                vec![Box::new(SleepingTestCase::new(2)) as Box<dyn TestCase<()> + Send>],
+            );
+            chapter.groups.push(TestGroup::new(
+                "Section 1.1".to_string(),
+                "".to_string(),
+                vec![
+                    Box::new(SleepingTestCase::new(3)) as Box<dyn TestCase<()> + Send>,
+                    Box::new(SleepingTestCase::new(4)),
+                ],
+            ));
+
+            let mut suite = TestSuite::new(
+                "Suite".to_string(),
+                "".to_string(),
+                vec![Box::new(SleepingTestCase::new(1)) as Box<dyn TestCase<()> + Send>],
+            );
+            suite.groups.push(chapter);
+            suite.groups.push(TestGroup::new(
+                "Chapter 2".to_string(),
+                "".to_string(),
+                vec![Box::new(SleepingTestCase::new(5)) as Box<dyn TestCase<()> + Send>],
+            ));
+            suite
+        }
+
+        for results in [
+            TestRunner::new().run(|_| (), suite()),
+            TestRunner::new().run_parallel(|_| (), suite()),
+        ] {
+            let titles = |results: &Vec<TestCaseResult>| -> Vec<String> {
+                results.iter().map(|r| r.title().to_string()).collect()
+            };
+
+            assert_eq!(titles(results.results()), vec!["Sleep 1"]);
+
+            let chapter_1 = &results.groups()[0];
+            assert_eq!(chapter_1.title(), "Chapter 1");
+            assert_eq!(titles(chapter_1.results()), vec!["Sleep 2"]);
+
+            let section = &chapter_1.groups()[0];
+            assert_eq!(section.title(), "Section 1.1");
+            assert_eq!(titles(section.results()), vec!["Sleep 3", "Sleep 4"]);
+
+            let chapter_2 = &results.groups()[1];
+            assert_eq!(chapter_2.title(), "Chapter 2");
+            assert_eq!(titles(chapter_2.results()), vec!["Sleep 5"]);
+
+            assert_eq!(results.all_results().count(), 5);
+        }
+    }
+
+    /// Verify that filtering removes the groups that no longer have any test cases.
+    #[test]
+    fn filtering_removes_empty_groups() {
+        let mut suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![Box::new(SleepingTestCase::new(1)) as Box<dyn TestCase<()>>],
+        );
+        suite.groups.push(TestGroup::new(
+            "Group".to_string(),
+            "".to_string(),
+            vec![Box::new(PanickingTestCase) as Box<dyn TestCase<()>>],
+        ));
+        suite.apply_filter(&TestFilter::Substring("Sleep".to_string()));
+
+        assert!(suite.groups.is_empty());
+        assert_eq!(suite.filtered_out_count(), 1);
+    }
+
+    /// Verify that creating the type to test and running the test case are timed separately, and
+    /// that the entire suite is timed.
+    #[test]
+    fn durations_are_measured() {
+        fn suite() -> TestSuite<(), dyn TestCase<()> + Send> {
+            TestSuite::new(
+                "Suite".to_string(),
+                "".to_string(),
+                vec![Box::new(SleepingTestCase::new(3)) as Box<dyn TestCase<()> + Send>],
+            )
+        }
+        let create = || std::thread::sleep(Duration::from_millis(20));
+
+        for results in [
+            TestRunner::new().run(|_| create(), suite()),
+            TestRunner::new().run_parallel(move |_| create(), suite()),
+        ] {
+            let duration = results.results()[0].duration().unwrap();
+
+            assert!(duration.setup() >= Duration::from_millis(20));
+            assert!(duration.run() >= Duration::from_millis(30));
+            assert!(results.duration().unwrap() >= duration.total());
+        }
+    }
+
+    /// Verify that every attached visualizer is told when the suite starts, as each test case
+    /// starts and finishes and when the suite finishes.
+    #[test]
+    fn visualizers_are_told_about_each_event() {
+        fn suite() -> TestSuite<(), dyn TestCase<()> + Send> {
+            TestSuite::new(
+                "Suite".to_string(),
+                "".to_string(),
+                vec![
+                    Box::new(SleepingTestCase::new(1)) as Box<dyn TestCase<()> + Send>,
+                    Box::new(PanickingTestCase),
+                ],
+            )
+        }
+
+        let expected_events = vec![
+            "started Suite with 2 test cases",
+            "started Sleep",
+            "finished Sleep",
+            "started Panics",
+            "finished Panics",
+            "finished Suite",
+        ];
+
+        let events = Rc::new(RefCell::new(vec![]));
+        let other_events = Rc::new(RefCell::new(vec![]));
+        TestRunner::new()
+            .with_visualizer(RecordingVisualizer(events.clone()))
+            .with_visualizer(RecordingVisualizer(other_events.clone()))
+            .run(|_| (), suite());
+        assert_eq!(*events.borrow(), expected_events);
+        assert_eq!(*other_events.borrow(), expected_events);
+
+        let events = Rc::new(RefCell::new(vec![]));
+        TestRunner::new()
+            .with_worker_count(NonZeroUsize::new(1).unwrap())
+            .with_visualizer(RecordingVisualizer(events.clone()))
+            .run_parallel(|_| (), suite());
+        assert_eq!(*events.borrow(), expected_events);
+    }
+
+    /// Verify that a test case's finished event has the same title as its started event, even
+    /// when its result has a different title and several test cases run at once.
+    #[test]
+    fn finished_titles_match_started_titles() {
+        let test_cases: Vec<Box<dyn TestCase<()> + Send>> = vec![
+            Box::new(SleepingTestCase::new(3)),
+            Box::new(PanickingTestCase),
+            Box::new(SleepingTestCase::new(1)),
+            Box::new(PanickingTestCase),
+        ];
+        let suite = TestSuite::new("Suite".to_string(), "".to_string(), test_cases);
+
+        let events = Rc::new(RefCell::new(vec![]));
+        TestRunner::new()
+            .with_worker_count(NonZeroUsize::new(4).unwrap())
+            .with_visualizer(RecordingVisualizer(events.clone()))
+            .run_parallel(|_| (), suite);
+
+        let titles = |prefix: &str| -> Vec<String> {
+            let mut titles: Vec<String> = events
+                .borrow()
+                .iter()
+                .filter_map(|event| event.strip_prefix(prefix))
+                .filter(|title| *title != "Suite" && !title.starts_with("Suite with"))
+                .map(|title| title.to_string())
+                .collect();
+            titles.sort();
+            titles
+        };
+        assert_eq!(
+            titles("started "),
+            vec!["Panics", "Panics", "Sleep", "Sleep"]
+        );
+        assert_eq!(titles("finished "), titles("started "));
+    }
+
+    /// Verify that a visualizer's output is written once the suite finishes.
+    #[test]
+    fn write_visualization() {
+        let suite = TestSuite::new(
+            "Suite".to_string(),
+            "".to_string(),
+            vec![Box::new(SleepingTestCase::new(1)) as Box<dyn TestCase<()>>],
+        );
+        let results = TestRunner::new().run(|_| (), suite);
+
+        let mut visualizer = crate::ViewSimpleText::new().write_to(Vec::new());
+        visualizer.suite_finished(&results);
+
+        let written = String::from_utf8(visualizer.into_writer()).unwrap();
+        assert_eq!(
+            written,
+            format!(
+                "{}\n",
+                crate::ViewSimpleText::new().process_test_results(&results)
+            )
+        );
+    }
+
+    /// Verify that every test case is run against each implementation in the matrix.
+    #[test]
+    fn matrix() {
+        fn suite() -> TestSuite<()> {
+            TestSuite::new(
+                "Suite".to_string(),
+                "".to_string(),
+                vec![Box::new(SleepingTestCase::new(0)) as Box<dyn TestCase<()>>],
+            )
+        }
+        let events = Rc::new(RefCell::new(vec![]));
+        let matrix = TestMatrix::new(suite)
+            .with_implementation("Works", |_| ())
+            .with_implementation("Broken", |_| panic!("could not create"));
+
+        let results = TestRunner::new()
+            .with_visualizer(RecordingVisualizer(events.clone()))
+            .run_matrix(matrix);
+
+        assert_eq!(results.suite_title(), "Suite");
+        let outcomes: Vec<(&str, bool)> = results
+            .implementations()
+            .iter()
+            .map(|implementation| (implementation.name(), implementation.results().did_pass()))
+            .collect();
+        assert_eq!(outcomes, vec![("Works", true), ("Broken", false)]);
+
+        let events = events.borrow();
+        assert_eq!(events[0], "started Suite (Works) with 1 test cases");
+        assert_eq!(events[4], "started Suite (Broken) with 1 test cases");
+    }
+
+    struct RecordingVisualizer(Rc<RefCell<Vec<String>>>);
+    impl Visualizer for RecordingVisualizer {
+        fn suite_started(&mut self, suite: &TestSuiteInfo) {
+            self.0.borrow_mut().push(format!(
+                "started {} with {} test cases",
+                suite.title(),
+                suite.test_case_count()
+            ));
+        }
+
+        fn test_case_started(&mut self, title: &str) {
+            self.0.borrow_mut().push(format!("started {}", title));
+        }
+
+        fn test_case_finished(&mut self, title: &str, _: &TestCaseResult) {
+            self.0.borrow_mut().push(format!("finished {}", title));
+        }
+
+        fn suite_finished(&mut self, test_results: &TestCaseResults) {
+            self.0
+                .borrow_mut()
+                .push(format!("finished {}", test_results.suite_title()));
+        }
+    }
+
+    struct PanickingTestCase;
+    impl TestCase<()> for PanickingTestCase {
+        fn run(self: Box<Self>, _: ()) -> TestCaseResult {
+            panic!("oh no")
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn title(&self) -> &str {
+            "Panics"
+        }
+
+        fn description(&self) -> &str {
+            "Always panics."
+        }
+    }
+
+    struct SleepingTestCase {
+        millis: u64,
+        timeout: Option<Duration>,
+        tags: Vec<String>,
+    }
+    impl SleepingTestCase {
+        fn new(millis: u64) -> Self {
+            SleepingTestCase {
+                millis,
+                timeout: None,
+                tags: vec![],
+            }
+        }
+
+        fn with_tags(mut self, tags: &[&str]) -> Self {
+            self.tags = tags.iter().map(|tag| tag.to_string()).collect();
+            self
+        }
+
+        fn with_timeout(mut self, timeout: Duration) -> Self {
+            self.timeout = Some(timeout);
+            self
+        }
+    }
+    impl TestCase<()> for SleepingTestCase {
+        fn run(self: Box<Self>, _: ()) -> TestCaseResult {
+            std::thread::sleep(Duration::from_millis(self.millis * 10));
+            TestCaseResult::new(
+                format!("Sleep {}", self.millis),
+                "".to_string(),
+                TestOutcome::Passed,
+            )
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn title(&self) -> &str {
+            "Sleep"
+        }
+
+        fn tags(&self) -> &[String] {
+            &self.tags
+        }
+
+        fn timeout(&self) -> Option<Duration> {
+            self.timeout
+        }
+    }
+
+    struct ExpectedToFail<T>(T);
+    impl<T: TestCase<()> + 'static> TestCase<()> for ExpectedToFail<T> {
+        fn run(self: Box<Self>, type_to_test: ()) -> TestCaseResult {
+            Box::new(self.0).run(type_to_test)
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn expect_failure(&self) -> bool {
+            true
+        }
+    }
+
+    struct NeverFinishesTestCase;
+    impl TestCase<()> for NeverFinishesTestCase {
+        fn run(self: Box<Self>, _: ()) -> TestCaseResult {
+            loop {
+                std::thread::park();
+            }
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+}
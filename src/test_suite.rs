@@ -1,26 +1,81 @@
-use crate::TestCase;
+use crate::{TagFilter, TestCase, TestFilter};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+pub use self::test_group::*;
+
+mod test_group;
 
 /// A test suite to run.
-pub struct TestSuite<TypeToTest> {
+///
+/// The `Case` type defaults to `dyn TestCase<TypeToTest>`. Suites of
+/// `dyn TestCase<TypeToTest> + Send` test cases can be run in parallel using
+/// [`crate::TestRunner::run_parallel`].
+pub struct TestSuite<TypeToTest, Case: ?Sized = dyn TestCase<TypeToTest>> {
     /// The test suite's title
     pub title: String,
     /// The test suite's description
     pub description: String,
-    /// The test cases
-    pub test_cases: Vec<Box<dyn TestCase<TypeToTest>>>,
+    /// The test cases that do not belong to a group
+    pub test_cases: Vec<Box<Case>>,
+    /// Groups of related test cases, such as the chapters of a specification
+    pub groups: Vec<TestGroup<TypeToTest, Case>>,
+    /// How long each test case is allowed to run before it is considered to have timed out.
+    ///
+    /// Individual test cases can override this using [`TestCase::timeout`].
+    /// Defaults to `None`, meaning that test cases can run for as long as they need.
+    pub timeout: Option<Duration>,
+    filtered_out_count: usize,
+    type_to_test: PhantomData<fn(TypeToTest)>,
 }
 
-impl<TypeToTest> TestSuite<TypeToTest> {
+impl<TypeToTest, Case: ?Sized + TestCase<TypeToTest>> TestSuite<TypeToTest, Case> {
     /// Create a new test suite.
-    pub fn new(
-        title: String,
-        description: String,
-        test_cases: Vec<Box<dyn TestCase<TypeToTest>>>,
-    ) -> Self {
+    pub fn new(title: String, description: String, test_cases: Vec<Box<Case>>) -> Self {
         TestSuite {
             title,
             description,
             test_cases,
+            groups: vec![],
+            timeout: None,
+            filtered_out_count: 0,
+            type_to_test: PhantomData,
         }
     }
+
+    /// The number of test cases in the suite, including those in nested groups.
+    pub fn test_case_count(&self) -> usize {
+        self.test_cases.len()
+            + self
+                .groups
+                .iter()
+                .map(|group| group.test_case_count())
+                .sum::<usize>()
+    }
+
+    /// Remove the test cases whose [`TestCase::title`]s do not match the filter.
+    ///
+    /// Groups that no longer contain any test cases are removed.
+    pub fn apply_filter(&mut self, filter: &TestFilter) {
+        self.filtered_out_count +=
+            test_group::retain_test_cases(&mut self.test_cases, &mut self.groups, &|test_case| {
+                filter.matches(test_case.title())
+            });
+    }
+
+    /// Remove the test cases whose [`TestCase::tags`] do not match the filter.
+    ///
+    /// Groups that no longer contain any test cases are removed.
+    pub fn apply_tag_filter(&mut self, tag_filter: &TagFilter) {
+        self.filtered_out_count +=
+            test_group::retain_test_cases(&mut self.test_cases, &mut self.groups, &|test_case| {
+                tag_filter.matches(test_case.tags())
+            });
+    }
+
+    /// The number of test cases that were removed by [`TestSuite::apply_filter`] and
+    /// [`TestSuite::apply_tag_filter`].
+    pub fn filtered_out_count(&self) -> usize {
+        self.filtered_out_count
+    }
 }
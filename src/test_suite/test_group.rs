@@ -0,0 +1,66 @@
+use crate::TestCase;
+use std::marker::PhantomData;
+
+/// A group of related test cases within a [`crate::TestSuite`], such as a chapter or section of a
+/// specification.
+///
+/// Groups can contain other groups, and the hierarchy is preserved in the
+/// [`crate::TestCaseResults`].
+pub struct TestGroup<TypeToTest, Case: ?Sized = dyn TestCase<TypeToTest>> {
+    /// The group's title
+    pub title: String,
+    /// The group's description
+    pub description: String,
+    /// The test cases that belong directly to this group
+    pub test_cases: Vec<Box<Case>>,
+    /// The groups nested within this group
+    pub groups: Vec<TestGroup<TypeToTest, Case>>,
+    type_to_test: PhantomData<fn(TypeToTest)>,
+}
+
+impl<TypeToTest, Case: ?Sized + TestCase<TypeToTest>> TestGroup<TypeToTest, Case> {
+    /// Create a new group of test cases.
+    pub fn new(title: String, description: String, test_cases: Vec<Box<Case>>) -> Self {
+        TestGroup {
+            title,
+            description,
+            test_cases,
+            groups: vec![],
+            type_to_test: PhantomData,
+        }
+    }
+
+    /// The number of test cases in this group and all of its nested groups.
+    pub fn test_case_count(&self) -> usize {
+        self.test_cases.len()
+            + self
+                .groups
+                .iter()
+                .map(|group| group.test_case_count())
+                .sum::<usize>()
+    }
+}
+
+/// Keep the test cases that match the predicate, searching through nested groups. Groups that no
+/// longer have any test cases are removed.
+///
+/// Returns the number of test cases that were removed.
+pub(crate) fn retain_test_cases<TypeToTest, Case>(
+    test_cases: &mut Vec<Box<Case>>,
+    groups: &mut Vec<TestGroup<TypeToTest, Case>>,
+    keep: &dyn Fn(&Case) -> bool,
+) -> usize
+where
+    Case: ?Sized + TestCase<TypeToTest>,
+{
+    let test_case_count = test_cases.len();
+    test_cases.retain(|test_case| keep(test_case));
+    let mut removed_count = test_case_count - test_cases.len();
+
+    for group in groups.iter_mut() {
+        removed_count += retain_test_cases(&mut group.test_cases, &mut group.groups, keep);
+    }
+    groups.retain(|group| group.test_case_count() > 0);
+
+    removed_count
+}
@@ -1,5 +1,233 @@
+use crate::{BaselineChange, BaselineComparison, TestCaseResult, TestCaseResults, TestOutcome};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::time::Duration;
+
+pub use self::html_report::*;
 pub use self::view_html::*;
+#[cfg(feature = "serde")]
+pub use self::view_json::*;
+pub use self::view_junit_xml::*;
+pub use self::view_matrix_html::*;
+pub use self::view_matrix_text::*;
 pub use self::view_simple_text::*;
+pub use self::write_visualization::*;
 
+mod html_report;
 mod view_html;
+#[cfg(feature = "serde")]
+mod view_json;
+mod view_junit_xml;
+mod view_matrix_html;
+mod view_matrix_text;
 mod view_simple_text;
+mod write_visualization;
+
+/// Visualizes the results of a test suite.
+///
+/// A visualizer can be attached to a [`crate::TestRunner`] using
+/// [`crate::TestRunner::with_visualizer`], in which case it is told when the suite starts, as each
+/// test case starts and finishes and when the suite finishes. This allows progress to be shown
+/// while a long running suite is still running.
+///
+/// ```
+/// # use conformer::{TestRunner, ViewHtml, ViewSimpleText, Visualizer};
+/// let runner = TestRunner::new()
+///     .with_visualizer(ViewSimpleText::new().write_to(std::io::stdout()))
+///     .with_visualizer(ViewHtml::new().write_to(Vec::new()));
+/// ```
+pub trait Visualizer {
+    /// Visualize the results of a test suite that has finished running.
+    ///
+    /// Defaults to an empty visualization, for visualizers that only report events as they happen
+    /// such as a progress bar.
+    fn process_test_results(&self, _test_results: &TestCaseResults) -> String {
+        "".to_string()
+    }
+
+    /// Called before any of the suite's test cases are run.
+    fn suite_started(&mut self, _suite: &TestSuiteInfo) {}
+
+    /// Called before each test case is run, with the test case's [`crate::TestCase::title`].
+    ///
+    /// When test cases are run in parallel several test cases can be running at once.
+    fn test_case_started(&mut self, _title: &str) {}
+
+    /// Called after each test case has finished, in the order that they finished, with the same
+    /// title that [`Visualizer::test_case_started`] was called with.
+    ///
+    /// The title can differ from the result's [`TestCaseResult::title`], so use it to tell which
+    /// of the running test cases finished.
+    fn test_case_finished(&mut self, _title: &str, _result: &TestCaseResult) {}
+
+    /// Called after all of the suite's test cases have finished.
+    fn suite_finished(&mut self, _test_results: &TestCaseResults) {}
+
+    /// Write the visualization to the writer when the suite finishes.
+    fn write_to<W: Write>(self, writer: W) -> WriteVisualization<Self, W>
+    where
+        Self: Sized,
+    {
+        WriteVisualization::new(self, writer)
+    }
+}
+
+/// Information about a test suite that is about to be run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestSuiteInfo {
+    title: String,
+    description: String,
+    test_case_count: usize,
+    filtered_out_count: usize,
+}
+
+impl TestSuiteInfo {
+    #[allow(missing_docs)]
+    pub fn new(
+        title: String,
+        description: String,
+        test_case_count: usize,
+        filtered_out_count: usize,
+    ) -> Self {
+        TestSuiteInfo {
+            title,
+            description,
+            test_case_count,
+            filtered_out_count,
+        }
+    }
+
+    /// The test suite's title.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The test suite's description.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The number of test cases that are about to be run, including those in groups.
+    pub fn test_case_count(&self) -> usize {
+        self.test_case_count
+    }
+
+    /// The number of test cases that will not be run because they were filtered out.
+    pub fn filtered_out_count(&self) -> usize {
+        self.filtered_out_count
+    }
+}
+
+/// A short label for the test case's outcome, such as "ok" or "FAILED".
+///
+/// Failures that were caused by a panic or a timeout are labeled as such.
+fn result_label(result: &TestCaseResult) -> &'static str {
+    match result.outcome() {
+        TestOutcome::Failed if result.panic().is_some() => "PANICKED",
+        TestOutcome::Failed if result.timeout().is_some() => "TIMED OUT",
+        TestOutcome::Passed => "ok",
+        TestOutcome::Failed => "FAILED",
+        TestOutcome::Skipped => "skipped",
+        TestOutcome::Ignored => "ignored",
+        TestOutcome::ExpectedFailure => "expected failure",
+        TestOutcome::UnexpectedPass => "UNEXPECTED PASS",
+    }
+}
+
+/// The number of test cases with each outcome, such as "2 passed; 1 failed; 1 skipped".
+///
+/// The passed and failed counts are always included, other outcomes are only included when at
+/// least one test case had that outcome. The number of test cases that were filtered out is
+/// included when any were filtered out.
+fn outcome_counts<'a>(
+    results: impl IntoIterator<Item = &'a TestCaseResult>,
+    filtered_out_count: usize,
+) -> String {
+    let mut outcome_counts = [0; TestOutcome::ALL.len()];
+    for result in results {
+        let idx = TestOutcome::ALL
+            .iter()
+            .position(|outcome| *outcome == result.outcome())
+            .unwrap();
+        outcome_counts[idx] += 1;
+    }
+
+    let mut counts: Vec<String> = TestOutcome::ALL
+        .iter()
+        .zip(outcome_counts.iter())
+        .filter_map(|(outcome, count)| {
+            let count = *count;
+
+            let name = match outcome {
+                TestOutcome::Passed => "passed",
+                TestOutcome::Failed => "failed",
+                _ if count == 0 => return None,
+                TestOutcome::Skipped => "skipped",
+                TestOutcome::Ignored => "ignored",
+                TestOutcome::ExpectedFailure if count == 1 => "expected failure",
+                TestOutcome::ExpectedFailure => "expected failures",
+                TestOutcome::UnexpectedPass if count == 1 => "unexpected pass",
+                TestOutcome::UnexpectedPass => "unexpected passes",
+            };
+
+            Some(format!("{} {}", count, name))
+        })
+        .collect();
+
+    if filtered_out_count > 0 {
+        counts.push(format!("{} filtered out", filtered_out_count));
+    }
+
+    counts.join("; ")
+}
+
+/// The number of test cases with each change since the baseline, such as
+/// "1 regressed; 2 still passing". Changes that no test case had are left out.
+fn baseline_change_counts(comparison: &BaselineComparison) -> String {
+    let counts: Vec<String> = BaselineChange::ALL
+        .iter()
+        .map(|change| (change, comparison.change_count(*change)))
+        .filter(|(_, count)| *count > 0)
+        .map(|(change, count)| format!("{} {}", count, change.label()))
+        .collect();
+
+    counts.join("; ")
+}
+
+/// A short, human readable duration, such as "12ms" or "1.50s".
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+/// The title used for the group of test case results that do not have any tags.
+const UNTAGGED: &str = "untagged";
+
+/// Group the results by their tags, sorted by tag.
+///
+/// A result with multiple tags appears in each of its tags' groups. Results without any tags are
+/// grouped last, under [`UNTAGGED`].
+fn group_by_tag(test_results: &TestCaseResults) -> Vec<(&str, Vec<&TestCaseResult>)> {
+    let mut tagged: BTreeMap<&str, Vec<&TestCaseResult>> = BTreeMap::new();
+    let mut untagged = vec![];
+
+    for result in test_results.all_results() {
+        if result.tags().is_empty() {
+            untagged.push(result);
+        }
+
+        for tag in result.tags() {
+            tagged.entry(tag.as_str()).or_default().push(result);
+        }
+    }
+
+    let mut groups: Vec<(&str, Vec<&TestCaseResult>)> = tagged.into_iter().collect();
+    if !untagged.is_empty() {
+        groups.push((UNTAGGED, untagged));
+    }
+
+    groups
+}
@@ -0,0 +1,168 @@
+use crate::{TestCaseResults, ViewHtml};
+use std::path::{Path, PathBuf};
+
+/// Writes an HTML report to a directory, with an index page that lists the test cases and a page
+/// for each test case.
+///
+/// Unlike [`ViewHtml::process_test_results`], which embeds every image and attachment in a single
+/// page, each image and attachment is written to its own file and the pages link to them by their
+/// relative paths. This keeps the pages small for suites that produce many large images.
+///
+/// ```no_run
+/// # use conformer::{HtmlReport, TestCaseResult, TestCaseResults, TestOutcome};
+/// # let test_results = TestCaseResults::new("".to_string(), "".to_string(), vec![
+/// #     TestCaseResult::new("".to_string(), "".to_string(), TestOutcome::Passed)
+/// # ]);
+/// let index = HtmlReport::new("target/conformer-report").write(&test_results);
+/// println!("Report written to {}", index.display());
+/// ```
+///
+/// The directory is laid out as:
+///
+/// ```text
+/// index.html
+/// case-0.html
+/// case-0/Framebuffer_Expected.png
+/// case-1.html
+/// ...
+/// ```
+pub struct HtmlReport {
+    directory: PathBuf,
+    view: ViewHtml,
+}
+
+impl HtmlReport {
+    /// Write the report to the directory, creating it if it does not exist.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        HtmlReport {
+            directory: directory.into(),
+            view: ViewHtml::new(),
+        }
+    }
+
+    /// Render the pages using the view, such as one that groups the test cases by tag or compares
+    /// them against a baseline.
+    pub fn with_view(mut self, view: ViewHtml) -> Self {
+        self.view = view;
+        self
+    }
+
+    /// The directory that the report is written to.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Write the index page, each test case's page and their images and attachments, returning
+    /// the path of the index page.
+    ///
+    /// Files from a previous report are overwritten but not removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a file could not be written.
+    pub fn write(&self, test_results: &TestCaseResults) -> PathBuf {
+        let report = self.view.render_report(test_results);
+
+        let pages = report
+            .pages
+            .iter()
+            .map(|(path, page)| (path.as_str(), page.as_bytes()));
+        let files = report
+            .files
+            .iter()
+            .map(|(path, bytes)| (path.as_str(), *bytes));
+
+        for (path, contents) in pages.chain(files) {
+            write_file(&self.directory.join(path), contents);
+        }
+
+        let index = self.directory.join("index.html");
+        write_file(&index, report.index.as_bytes());
+
+        index
+    }
+}
+
+fn write_file(path: &Path, contents: &[u8]) {
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory)
+            .unwrap_or_else(|error| panic!("{}: {}", directory.display(), error));
+    }
+
+    std::fs::write(path, contents).unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Attachment, TestCaseResult, TestOutcome};
+
+    /// Verify that each test case gets its own page, and that attachments are written to their
+    /// own files and linked to by their relative paths.
+    #[test]
+    fn writes_pages_and_attachments() {
+        let directory = temp_directory("writes_pages_and_attachments");
+
+        let mut result = TestCaseResult::new(
+            "Render quads".to_string(),
+            "".to_string(),
+            TestOutcome::Failed,
+        );
+        result.push_attachment(Attachment::text(
+            "gpu log.txt".to_string(),
+            "OOM".to_string(),
+        ));
+        let test_results =
+            TestCaseResults::new("Renderer".to_string(), "".to_string(), vec![result]);
+
+        let index = HtmlReport::new(&directory).write(&test_results);
+
+        let index = std::fs::read_to_string(index).unwrap();
+        assert!(index.contains(r#"<a href="case-0.html">View the test case</a>"#));
+        assert!(!index.contains("case-0/gpu_log.txt"));
+
+        let page = std::fs::read_to_string(directory.join("case-0.html")).unwrap();
+        assert!(page.contains(r#"<a href="index.html">Renderer</a>"#));
+        assert!(page.contains(r#"<a href="case-0/gpu_log.txt" download="gpu log.txt">"#));
+
+        let attachment = std::fs::read(directory.join("case-0/gpu_log.txt")).unwrap();
+        assert_eq!(attachment, b"OOM");
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    /// Verify that attachments whose names would be a directory, such as `..`, are still written
+    /// to a file within the test case's directory.
+    #[test]
+    fn directory_attachment_names() {
+        let directory = temp_directory("directory_attachment_names");
+
+        let mut result = TestCaseResult::new(
+            "Render quads".to_string(),
+            "".to_string(),
+            TestOutcome::Failed,
+        );
+        for name in &["", ".", ".."] {
+            result.push_attachment(Attachment::text(name.to_string(), "OOM".to_string()));
+        }
+        let test_results =
+            TestCaseResults::new("Renderer".to_string(), "".to_string(), vec![result]);
+
+        HtmlReport::new(&directory).write(&test_results);
+
+        for path in &["attachment", "1-attachment", "2-attachment"] {
+            let attachment = std::fs::read(directory.join("case-0").join(path)).unwrap();
+            assert_eq!(attachment, b"OOM");
+        }
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    fn temp_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir()
+            .join("conformer-html-report")
+            .join(format!("{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+}
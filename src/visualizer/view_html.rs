@@ -1,70 +1,759 @@
-use crate::TestCaseResults;
+use crate::visualizer::{
+    baseline_change_counts, format_duration, group_by_tag, outcome_counts, result_label,
+};
+use crate::{
+    escape_html, Attachment, BaselineChange, BaselineComparison, DiffLine, ImageDiff,
+    TestCaseResult, TestCaseResults, TestCheck, TestFailure, TestGroupResults, TestOutcome,
+    Visualizer,
+};
 
-const RED: &'static str = "rgb(255, 0, 0)";
-const GREEN: &'static str = "rgb(50, 205, 50)";
-const BLACK: &'static str = "rgb(0, 0, 0)";
+pub(super) const RED: &str = "rgb(255, 0, 0)";
+pub(super) const GREEN: &str = "rgb(50, 205, 50)";
+pub(super) const ORANGE: &str = "rgb(255, 140, 0)";
+pub(super) const GRAY: &str = "rgb(128, 128, 128)";
+pub(super) const BLACK: &str = "rgb(0, 0, 0)";
+
+/// Lays out the table of contents, along with the status filter and search box, in a sidebar
+/// next to the test cases, and hides the test cases that were filtered out.
+const STYLE: &str = r#"
+      [hidden] { display: none !important; }
+      body { display: flex; margin: 0px; font-family: sans-serif; }
+      #toc { position: sticky; top: 0px; flex-shrink: 0; box-sizing: border-box; width: 250px; height: 100vh; overflow-y: auto; padding: 10px; border-right: 1px solid rgb(200, 200, 200); }
+      #toc a { display: block; margin-bottom: 4px; font-size: 14px; text-decoration: none; }
+      main { flex-grow: 1; min-width: 0px; padding: 0px 20px; }
+      #toc select, #toc input { display: block; box-sizing: border-box; width: 100%; margin-bottom: 10px; }
+      .test-case > summary { cursor: pointer; }
+    "#;
+
+/// Filters the test cases by their status and by the search text, and expands a collapsed test
+/// case when it is jumped to from the table of contents.
+const SCRIPT: &str = r##"
+      var statusFilter = document.getElementById("status-filter");
+      var search = document.getElementById("search");
+
+      function filterTestCases() {
+        var query = search.value.toLowerCase();
+
+        document.querySelectorAll(".test-case").forEach(function (testCase) {
+          var matchesStatus = statusFilter.value === "all" || testCase.dataset.status === statusFilter.value;
+          var matchesQuery = testCase.querySelector("summary").textContent.toLowerCase().indexOf(query) !== -1;
+
+          testCase.hidden = !(matchesStatus && matchesQuery);
+          document.querySelector('#toc a[href="#' + testCase.id + '"]').hidden = testCase.hidden;
+        });
+      }
+
+      statusFilter.addEventListener("change", filterTestCases);
+      search.addEventListener("input", filterTestCases);
+
+      document.querySelectorAll("#toc a").forEach(function (link) {
+        link.addEventListener("click", function () {
+          document.getElementById(link.getAttribute("href").slice(1)).open = true;
+        });
+      });
+    "##;
+
+/// The metadata key that test cases used to set their visual before
+/// [`TestCaseResult::set_html_visual`]. Its value is still shown as the visual, without being
+/// escaped, for test cases that did not set one, but it is deprecated and will be removed.
+const LEGACY_HTML_VISUAL_KEY: &str = "html-visual";
 
 /// Constructs an HTML visualization of a test suite.
-pub struct ViewHtml {}
+#[derive(Default)]
+pub struct ViewHtml {
+    group_by_tag: bool,
+    baseline: Option<TestCaseResults>,
+}
 
 impl ViewHtml {
     #[allow(missing_docs)]
     pub fn new() -> Self {
-        ViewHtml {}
+        ViewHtml::default()
+    }
+
+    /// Group the test results by their tags, with a heading and pass and fail counts for each
+    /// tag.
+    ///
+    /// A test case with multiple tags is shown under each of its tags, and test cases without
+    /// any tags are shown last under "untagged".
+    pub fn with_group_by_tag(mut self, group_by_tag: bool) -> Self {
+        self.group_by_tag = group_by_tag;
+        self
+    }
+
+    /// Compare the test results to the results of a previous run, such as the last green build,
+    /// and list the test cases that regressed, were fixed, are new or were removed since then
+    /// above the test results.
+    ///
+    /// See [`BaselineComparison`].
+    pub fn with_baseline(mut self, baseline: TestCaseResults) -> Self {
+        self.baseline = Some(baseline);
+        self
     }
 
     /// Constructs an HTML visualization of a test suite.
+    ///
+    /// Each group of test cases gets a heading with its pass and fail counts, and nested groups
+    /// are indented beneath their parent group.
+    ///
+    /// The page is self-contained. A sticky table of contents links to each test case, passed
+    /// test cases start out collapsed, and the test cases can be filtered by their status or
+    /// searched by their title and description.
     pub fn process_test_results(&self, test_results: &TestCaseResults) -> String {
-        let mut output = format!(
-            r#"<html>
+        self.render(test_results, &mut Render::new(false))
+    }
+
+    /// Render the index page of a multi-file report, along with each test case's page and the
+    /// images and attachments that the pages link to.
+    ///
+    /// See [`crate::HtmlReport`].
+    pub(super) fn render_report<'a>(
+        &self,
+        test_results: &'a TestCaseResults,
+    ) -> RenderedReport<'a> {
+        let mut render = Render::new(true);
+        let index = self.render(test_results, &mut render);
+
+        let suite_title = escape_html(test_results.suite_title());
+        let pages = render
+            .pages
+            .into_iter()
+            .map(|(id, title, details)| {
+                let page = format!(
+                    r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="utf-8" />
+    <title>{title}</title>
+    <style>{style}</style>
+  </head>
   <body>
+    <main>
+    <p><a href="index.html">{suite_title}</a></p>{details}
+    </main>
+  </body>
+</html>"#,
+                    title = title,
+                    style = STYLE,
+                    suite_title = suite_title,
+                    details = details
+                );
+
+                (format!("{}.html", id), page)
+            })
+            .collect();
+
+        RenderedReport {
+            index,
+            pages,
+            files: render.files,
+        }
+    }
+
+    fn render<'a>(&self, test_results: &'a TestCaseResults, render: &mut Render<'a>) -> String {
+        let mut content = String::new();
+
+        if let Some(baseline) = &self.baseline {
+            content += &baseline_html(&BaselineComparison::new(baseline, test_results));
+        }
+
+        if self.group_by_tag {
+            for (tag, results) in group_by_tag(test_results) {
+                content += &format!(
+                    r#"
+
+    <h2>{tag} <small>({outcome_counts})</small></h2>"#,
+                    tag = escape_html(tag),
+                    outcome_counts = outcome_counts(results.iter().copied(), 0)
+                );
+
+                for test_result in results {
+                    content += &test_result_html(test_result, render);
+                }
+            }
+        } else {
+            for test_result in test_results.results() {
+                content += &test_result_html(test_result, render);
+            }
+
+            for group in test_results.groups() {
+                content += &group_results_html(group, 0, render);
+            }
+        }
+
+        let status_options: String = TestOutcome::ALL
+            .iter()
+            .map(|outcome| {
+                let (status, label) = status(*outcome);
+                format!(
+                    r#"
+        <option value="{status}">{label}</option>"#,
+                    status = status,
+                    label = label
+                )
+            })
+            .collect();
+
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="utf-8" />
+    <title>{title}</title>
+    <style>{style}</style>
+  </head>
+  <body>
+    <nav id="toc">
+      <select id="status-filter">
+        <option value="all">All</option>{status_options}
+      </select>
+      <input id="search" type="search" placeholder="Search test cases" />{toc}
+    </nav>
+    <main>
     <h1>{title}</h1>
+    <p>{description}</p>
+    <p>{outcome_counts}{duration}</p>{content}
+    </main>
+    <script>{script}</script>
+  </body>
+</html>"#,
+            title = escape_html(test_results.suite_title()),
+            style = STYLE,
+            toc = render.toc.concat(),
+            description = escape_html(test_results.suite_description()),
+            outcome_counts = outcome_counts(
+                test_results.all_results(),
+                test_results.filtered_out_count()
+            ),
+            duration = match test_results.duration() {
+                Some(duration) => format!("; finished in {}", format_duration(duration)),
+                None => "".to_string(),
+            },
+            status_options = status_options,
+            content = content,
+            script = SCRIPT
+        )
+    }
+}
+
+/// The pages and files of a multi-file report.
+pub(super) struct RenderedReport<'a> {
+    /// The page that lists every test case.
+    pub(super) index: String,
+    /// The file name and contents of each test case's page, such as "case-0.html".
+    pub(super) pages: Vec<(String, String)>,
+    /// The path of each image and attachment relative to the index page, along with its
+    /// contents.
+    pub(super) files: Vec<(String, &'a [u8])>,
+}
+
+/// What is collected while the test results are being rendered.
+struct Render<'a> {
+    /// The link to each test case in the table of contents.
+    toc: Vec<String>,
+    /// Whether images and attachments are written to their own files and each test case gets its
+    /// own page, instead of embedding everything in a single page.
+    multi_file: bool,
+    /// The id, title and details of each test case, when each test case gets its own page.
+    pages: Vec<(String, String, String)>,
+    /// The images and attachments, when they are written to their own files.
+    files: Vec<(String, &'a [u8])>,
+}
+
+impl<'a> Render<'a> {
+    fn new(multi_file: bool) -> Self {
+        Render {
+            toc: vec![],
+            multi_file,
+            pages: vec![],
+            files: vec![],
+        }
+    }
+
+    /// Where a page can find an image or attachment of the test case with the given id. This is
+    /// either a path relative to the index page or a `data:` URI.
+    fn src(&mut self, id: &str, name: &str, mime_type: &str, bytes: &'a [u8]) -> String {
+        if !self.multi_file {
+            return format!("data:{};base64,{}", mime_type, base64::encode(bytes));
+        }
+
+        let file_name = file_name(name);
+        let mut path = format!("{}/{}", id, file_name);
+        let mut duplicate = 1;
+        while self.files.iter().any(|(existing, _)| *existing == path) {
+            path = format!("{}/{}-{}", id, duplicate, file_name);
+            duplicate += 1;
+        }
+
+        self.files.push((path.clone(), bytes));
+        path
+    }
+}
+
+impl Visualizer for ViewHtml {
+    fn process_test_results(&self, test_results: &TestCaseResults) -> String {
+        ViewHtml::process_test_results(self, test_results)
+    }
+}
+
+fn baseline_html(comparison: &BaselineComparison) -> String {
+    let mut output = format!(
+        r#"
+
+    <h2>Changes since baseline <small>({change_counts})</small></h2>"#,
+        change_counts = baseline_change_counts(comparison)
+    );
+
+    let changed: Vec<_> = comparison
+        .entries()
+        .iter()
+        .filter(|entry| entry.change().is_change())
+        .collect();
+    if changed.is_empty() {
+        return output;
+    }
+
+    output += r#"
+    <ul>"#;
+    for entry in changed {
+        let color = match entry.change() {
+            BaselineChange::Regressed => RED,
+            BaselineChange::Fixed => GREEN,
+            _ => GRAY,
+        };
+
+        output += &format!(
+            r#"
+      <li style="color: {color};">{title} ({change})</li>"#,
+            color = color,
+            title = escape_html(&entry.path()),
+            change = entry.change().label()
+        );
+    }
+    output += r#"
+    </ul>"#;
+
+    output
+}
+
+fn group_results_html<'a>(
+    group: &'a TestGroupResults,
+    depth: usize,
+    render: &mut Render<'a>,
+) -> String {
+    // The suite's title is the <h1>, and HTML does not have headings past <h6>.
+    let heading = (depth + 2).min(6);
+
+    let mut output = format!(
+        r#"
+
+    <div style="margin-left: 20px;">
+    <h{heading}>{title} <small>({outcome_counts})</small></h{heading}>"#,
+        heading = heading,
+        title = escape_html(group.title()),
+        outcome_counts = outcome_counts(group.all_results(), 0)
+    );
+
+    if !group.description().is_empty() {
+        output += &format!(
+            r#"
     <p>{description}</p>"#,
-            title = test_results.suite_title(),
-            description = test_results.suite_description(),
+            description = escape_html(group.description())
         );
+    }
 
-        for test_result in test_results.results() {
-            let ok_or_failed = if test_result.did_pass() {
-                "(ok)"
-            } else {
-                "(FAILED)"
-            };
+    for test_result in group.results() {
+        output += &test_result_html(test_result, render);
+    }
 
-            output += &format!(
-                r#"
+    for nested_group in group.groups() {
+        output += &group_results_html(nested_group, depth + 1, render);
+    }
+
+    output += r#"
+    </div>"#;
+
+    output
+}
+
+/// The test case, collapsed if it passed, along with a link to it in the table of contents.
+///
+/// When each test case gets its own page, the test case is shown in full on its page and the
+/// index page links to it.
+fn test_result_html<'a>(test_result: &'a TestCaseResult, render: &mut Render<'a>) -> String {
+    let id = format!("case-{}", render.toc.len());
+    let title = escape_html(test_result.title());
+    let (status, _) = status(test_result.outcome());
+
+    render.toc.push(format!(
+        r##"
+      <a href="#{id}" style="color: {color};">{title}</a>"##,
+        id = id,
+        color = outcome_color(test_result.outcome()),
+        title = title
+    ));
+
+    let ok_or_failed = format!("({})", result_label(test_result));
+
+    let ok_or_passed_color = outcome_color(test_result.outcome());
+
+    let html_visual = if let Some(panic) = test_result.panic() {
+        format!(
+            r#"<pre style="color: {color};">{panic}</pre>"#,
+            color = RED,
+            panic = escape_html(&panic.to_string())
+        )
+    } else if let Some(timeout) = test_result.timeout() {
+        format!(
+            r#"<pre style="color: {color};">timed out after {timeout:?}</pre>"#,
+            color = RED,
+            timeout = timeout
+        )
+    } else if let Some(html_visual) = test_result.html_visual() {
+        html_visual.as_str().to_string()
+    } else if let Some(html_visual) = legacy_html_visual(test_result) {
+        html_visual.to_string()
+    } else {
+        metadata_html(test_result)
+    };
+
+    let checks = checks_html(test_result.checks());
+
+    // Highlight why the test case failed above its visual.
+    let failures: String = test_result
+        .all_failures()
+        .map(|failure| failure_html(failure) + "\n      ")
+        .collect();
 
-    <div style="margin-bottom: 20px;">
-      <div style="font-size: 24px; font-weight: bold;">
+    let image_diffs: String = test_result
+        .image_diffs()
+        .iter()
+        .map(|image_diff| image_diff_html(image_diff, &id, render) + "\n      ")
+        .collect();
+
+    let attachments = attachments_html(test_result.attachments(), &id, render);
+
+    let duration = match test_result.duration() {
+        Some(duration) => format!(
+            r#"
+        <small style="color: {color};">{total} ({setup} setup)</small>"#,
+            color = GRAY,
+            total = format_duration(duration.total()),
+            setup = format_duration(duration.setup())
+        ),
+        None => "".to_string(),
+    };
+
+    let details = |open: bool, body: &str| {
+        format!(
+            r#"
+
+    <details id="{id}" class="test-case" data-status="{status}" style="margin-bottom: 20px;"{open}>
+      <summary style="font-size: 24px; font-weight: bold;">
         {title}
-        <label style="color: {ok_or_passed_color};"> {ok_or_failed}</label>
+        <label style="color: {ok_or_passed_color};"> {ok_or_failed}</label>{duration}
         <p style="color: {description_color}; font-size: 14px; margin: 0px;">{description}</p>
-      </div>
-      {html_visual}
-    </div>"#,
-                title = test_result.title(),
-                description = test_result.description(),
-                ok_or_passed_color = if test_result.did_pass() { GREEN } else { RED },
-                description_color = if test_result.did_pass() { BLACK } else { RED },
-                ok_or_failed = ok_or_failed,
-                html_visual = test_result.metadata().get("html-visual").unwrap()
+      </summary>
+      {body}
+    </details>"#,
+            id = id,
+            status = status,
+            open = if open { " open" } else { "" },
+            title = title,
+            description = escape_html(test_result.description()),
+            ok_or_passed_color = ok_or_passed_color,
+            description_color = if test_result.did_pass() { BLACK } else { RED },
+            ok_or_failed = ok_or_failed,
+            duration = duration,
+            body = body
+        )
+    };
+
+    let open = test_result.outcome() != TestOutcome::Passed;
+    let body = format!(
+        "{checks}{failures}{image_diffs}{attachments}{html_visual}",
+        checks = checks,
+        failures = failures,
+        image_diffs = image_diffs,
+        attachments = attachments,
+        html_visual = html_visual
+    );
+
+    if render.multi_file {
+        let page = details(true, &body);
+        render.pages.push((id.clone(), title.clone(), page));
+
+        details(
+            open,
+            &format!(r#"<a href="{id}.html">View the test case</a>"#, id = id),
+        )
+    } else {
+        details(open, &body)
+    }
+}
+
+/// The color that a test case's outcome is shown in.
+pub(super) fn outcome_color(outcome: TestOutcome) -> &'static str {
+    match outcome {
+        TestOutcome::Passed => GREEN,
+        TestOutcome::Failed => RED,
+        TestOutcome::UnexpectedPass => ORANGE,
+        TestOutcome::Skipped | TestOutcome::Ignored | TestOutcome::ExpectedFailure => GRAY,
+    }
+}
+
+/// The value that the status filter uses for the outcome, and the outcome's name in the filter.
+fn status(outcome: TestOutcome) -> (&'static str, &'static str) {
+    match outcome {
+        TestOutcome::Passed => ("passed", "Passed"),
+        TestOutcome::Failed => ("failed", "Failed"),
+        TestOutcome::Skipped => ("skipped", "Skipped"),
+        TestOutcome::Ignored => ("ignored", "Ignored"),
+        TestOutcome::ExpectedFailure => ("expected-failure", "Expected failure"),
+        TestOutcome::UnexpectedPass => ("unexpected-pass", "Unexpected pass"),
+    }
+}
+
+/// A checklist of the test case's checks, with a check mark next to each check that passed and a
+/// cross next to each check that failed.
+fn checks_html(checks: &[TestCheck]) -> String {
+    if checks.is_empty() {
+        return "".to_string();
+    }
+
+    let mut output = r#"<ul style="list-style: none; padding-left: 0px;">"#.to_string();
+    for check in checks {
+        let (mark, color) = if check.did_pass() {
+            ("&#10003;", GREEN)
+        } else {
+            ("&#10007;", RED)
+        };
+
+        output += &format!(
+            r#"
+        <li style="color: {color};">{mark} {name}</li>"#,
+            color = color,
+            mark = mark,
+            name = escape_html(check.name())
+        );
+    }
+    output += r#"
+      </ul>
+      "#;
+
+    output
+}
+
+fn legacy_html_visual(test_result: &TestCaseResult) -> Option<&str> {
+    test_result
+        .metadata()
+        .get(LEGACY_HTML_VISUAL_KEY)
+        .map(String::as_str)
+}
+
+/// A table of the test case's metadata sorted by key, shown in place of a visual for test cases
+/// that did not set one.
+fn metadata_html(test_result: &TestCaseResult) -> String {
+    if test_result.metadata().is_empty() {
+        return "".to_string();
+    }
+
+    let mut metadata: Vec<(&String, &String)> = test_result.metadata().iter().collect();
+    metadata.sort();
+
+    let mut output = r#"<table style="font-size: 14px;">"#.to_string();
+    for (key, value) in metadata {
+        output += &format!(
+            r#"
+        <tr><th style="text-align: left; padding-right: 10px;">{key}</th><td>{value}</td></tr>"#,
+            key = escape_html(key),
+            value = escape_html(value)
+        );
+    }
+    output += r#"
+      </table>"#;
+
+    output
+}
+
+/// The expected, actual and diff images side by side, above their statistics.
+fn image_diff_html<'a>(image_diff: &'a ImageDiff, id: &str, render: &mut Render<'a>) -> String {
+    let mut figure = |png: &'a [u8], caption: &str| {
+        if png.is_empty() {
+            return format!(
+                r#"
+        <figure style="margin: 0px;"><figcaption>{caption} (no pixels)</figcaption></figure>"#,
+                caption = caption
             );
         }
 
-        output += &format!(
+        let name = format!("{} {}.png", image_diff.name(), caption);
+
+        format!(
             r#"
-  </body>
-</html>"#,
+        <figure style="margin: 0px;"><img src="{src}" style="image-rendering: pixelated;" /><figcaption>{caption}</figcaption></figure>"#,
+            src = escape_html(&render.src(id, &name, "image/png", png)),
+            caption = caption
+        )
+    };
+
+    format!(
+        r#"<div style="display: flex; gap: 10px;">{expected}{actual}{diff}
+      </div>
+      <p style="color: {color}; font-size: 14px;">{name}: {summary}</p>"#,
+        expected = figure(image_diff.expected_png(), "Expected"),
+        actual = figure(image_diff.actual_png(), "Actual"),
+        diff = figure(image_diff.diff_png(), "Difference"),
+        color = if image_diff.did_pass() { GREEN } else { RED },
+        name = escape_html(image_diff.name()),
+        summary = image_diff.summary()
+    )
+}
+
+/// Each of the test case's attachments above its name and size. Images are shown inline, text is
+/// shown in a `<pre>` and other attachments get a link to download them. In a multi-file report
+/// every attachment's name links to its file.
+fn attachments_html<'a>(
+    attachments: &'a [Attachment],
+    id: &str,
+    render: &mut Render<'a>,
+) -> String {
+    let mut output = String::new();
+
+    for attachment in attachments {
+        let is_inline = attachment.is_image() || attachment.is_text();
+
+        // Within a single page, inline attachments are not linked to, since the link would embed
+        // a second copy of the attachment.
+        let src = if render.multi_file || !attachment.is_text() {
+            // Parameters such as the charset are kept, but `data:` URIs cannot contain spaces.
+            let mime_type = attachment.mime_type().replace(' ', "");
+            escape_html(&render.src(id, attachment.name(), &mime_type, attachment.bytes()))
+        } else {
+            "".to_string()
+        };
+        let name = escape_html(attachment.name());
+        let link = if render.multi_file || !is_inline {
+            format!(
+                r#"<a href="{src}" download="{name}">{name}</a>"#,
+                src = src,
+                name = name
+            )
+        } else {
+            name
+        };
+
+        let content = if attachment.is_image() {
+            format!(r#"<img src="{src}" />"#, src = src)
+        } else if attachment.is_text() {
+            format!(
+                r#"<pre style="border-left: 4px solid {color}; padding-left: 10px;">{text}</pre>"#,
+                color = GRAY,
+                text = escape_html(&String::from_utf8_lossy(attachment.bytes()))
+            )
+        } else {
+            "".to_string()
+        };
+
+        output += &format!(
+            r#"<figure style="margin: 0px 0px 10px 0px;">{content}<figcaption style="font-size: 14px;">{link} ({size} bytes)</figcaption></figure>
+      "#,
+            content = content,
+            link = link,
+            size = attachment.bytes().len()
         );
+    }
+
+    output
+}
 
-        output
+/// The name of the file that an image or attachment is written to, with the characters that are
+/// not ASCII letters, digits, `-`, `_` or `.` replaced with `_`.
+///
+/// Names that are empty or only dots, such as `..`, would name a directory instead of a file, so
+/// they are written to "attachment" instead.
+fn file_name(name: &str) -> String {
+    if name.chars().all(|c| c == '.') {
+        return "attachment".to_string();
     }
+
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// The failure's message and details, followed by its expected and actual values. Multi-line
+/// values are shown as a diff, with expected lines in red and actual lines in green.
+fn failure_html(failure: &TestFailure) -> String {
+    let mut text = escape_html(failure.message());
+    if let Some(details) = failure.details() {
+        text += "\n";
+        text += &escape_html(details);
+    }
+
+    match failure.diff() {
+        Some(diff) => {
+            text += "\ndiff (- expected, + actual):";
+            for line in diff {
+                text += &match line {
+                    DiffLine::Same(line) => format!("\n  {}", escape_html(&line)),
+                    DiffLine::Expected(line) => format!(
+                        r#"
+<span style="color: {color};">- {line}</span>"#,
+                        color = RED,
+                        line = escape_html(&line)
+                    ),
+                    DiffLine::Actual(line) => format!(
+                        r#"
+<span style="color: {color};">+ {line}</span>"#,
+                        color = GREEN,
+                        line = escape_html(&line)
+                    ),
+                    DiffLine::Unchanged(count) => format!(
+                        r#"
+<span style="color: {color};">  ... {count} unchanged lines</span>"#,
+                        color = GRAY,
+                        count = count
+                    ),
+                    DiffLine::Omitted(count) => format!(
+                        r#"
+<span style="color: {color};">  ... {count} more lines, too many to diff</span>"#,
+                        color = GRAY,
+                        count = count
+                    ),
+                };
+            }
+        }
+        None => {
+            if let Some(expected) = failure.expected() {
+                text += &format!("\nexpected: {}", escape_html(expected));
+            }
+            if let Some(actual) = failure.actual() {
+                text += &format!("\n  actual: {}", escape_html(actual));
+            }
+        }
+    };
+
+    format!(
+        r#"<pre style="color: {color}; border-left: 4px solid {color}; padding-left: 10px;">{text}</pre>"#,
+        color = RED,
+        text = text
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::TestCaseResult;
+    use crate::{
+        Attachment, ImageDiffStats, TestCaseDuration, TestCasePanic, TestCaseResult, TrustedHtml,
+    };
+    use std::time::Duration;
 
     /// Verify the output of one passing test case.
     #[test]
@@ -72,21 +761,24 @@ mod tests {
         let test_results = test_suite_one(PassedOrFailed::Passed);
         let results = ViewHtml::new().process_test_results(&test_results);
 
-        let expected = r#"<html>
-  <body>
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(50, 205, 50);">Test Case Title</a>"##,
+            r##"
     <h1>My Test Suite Title</h1>
     <p>My Test Suite description.</p>
+    <p>1 passed; 0 failed</p>
 
-    <div style="margin-bottom: 20px;">
-      <div style="font-size: 24px; font-weight: bold;">
+    <details id="case-0" class="test-case" data-status="passed" style="margin-bottom: 20px;">
+      <summary style="font-size: 24px; font-weight: bold;">
         Test Case Title
         <label style="color: rgb(50, 205, 50);"> (ok)</label>
         <p style="color: rgb(0, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
-      </div>
+      </summary>
       <div><em>Test case html visualization here</em></div>
-    </div>
-  </body>
-</html>"#;
+    </details>"##,
+        );
 
         assert_eq!(results, expected)
     }
@@ -97,21 +789,24 @@ mod tests {
         let test_results = test_suite_one(PassedOrFailed::Failed);
         let results = ViewHtml::new().process_test_results(&test_results);
 
-        let expected = r#"<html>
-  <body>
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(255, 0, 0);">Test Case Title</a>"##,
+            r##"
     <h1>My Test Suite Title</h1>
     <p>My Test Suite description.</p>
+    <p>0 passed; 1 failed</p>
 
-    <div style="margin-bottom: 20px;">
-      <div style="font-size: 24px; font-weight: bold;">
+    <details id="case-0" class="test-case" data-status="failed" style="margin-bottom: 20px;" open>
+      <summary style="font-size: 24px; font-weight: bold;">
         Test Case Title
         <label style="color: rgb(255, 0, 0);"> (FAILED)</label>
         <p style="color: rgb(255, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
-      </div>
+      </summary>
       <div><em>Test case html visualization here</em></div>
-    </div>
-  </body>
-</html>"#;
+    </details>"##,
+        );
 
         assert_eq!(results, expected)
     }
@@ -122,41 +817,639 @@ mod tests {
         let test_results = test_suite_two(PassedOrFailed::Passed, PassedOrFailed::Failed);
         let results = ViewHtml::new().process_test_results(&test_results);
 
-        let expected = r#"<html>
-  <body>
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(50, 205, 50);">Test Case Title</a>
+      <a href="#case-1" style="color: rgb(255, 0, 0);">Test Case Title</a>"##,
+            r##"
     <h1>My Test Suite Title</h1>
     <p>My Test Suite description.</p>
+    <p>1 passed; 1 failed</p>
 
-    <div style="margin-bottom: 20px;">
-      <div style="font-size: 24px; font-weight: bold;">
+    <details id="case-0" class="test-case" data-status="passed" style="margin-bottom: 20px;">
+      <summary style="font-size: 24px; font-weight: bold;">
         Test Case Title
         <label style="color: rgb(50, 205, 50);"> (ok)</label>
         <p style="color: rgb(0, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
-      </div>
+      </summary>
       <div><em>Test case html visualization here</em></div>
-    </div>
+    </details>
+
+    <details id="case-1" class="test-case" data-status="failed" style="margin-bottom: 20px;" open>
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(255, 0, 0);"> (FAILED)</label>
+        <p style="color: rgb(255, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      <div><em>Test case html visualization here</em></div>
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that a test case that panicked is distinguished from a test case that failed.
+    #[test]
+    fn one_test_case_panicked() {
+        let panicked = TestCaseResult::panicked(
+            test_case_title(),
+            test_case_description(),
+            TestCasePanic::new("oh no".to_string(), Some("src/lib.rs:10:5".to_string())),
+        );
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![panicked]);
+        let results = ViewHtml::new().process_test_results(&test_results);
+
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(255, 0, 0);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>0 passed; 1 failed</p>
+
+    <details id="case-0" class="test-case" data-status="failed" style="margin-bottom: 20px;" open>
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(255, 0, 0);"> (PANICKED)</label>
+        <p style="color: rgb(255, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      <pre style="color: rgb(255, 0, 0);">panicked at 'oh no', src/lib.rs:10:5</pre>
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that a test case that timed out is distinguished from a test case that failed.
+    #[test]
+    fn one_test_case_timed_out() {
+        let timed_out = TestCaseResult::timed_out(
+            test_case_title(),
+            test_case_description(),
+            Duration::from_millis(1500),
+        );
+        let test_results = TestCaseResults::new(
+            test_suite_title(),
+            test_suite_description(),
+            vec![timed_out],
+        );
+        let results = ViewHtml::new().process_test_results(&test_results);
+
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(255, 0, 0);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>0 passed; 1 failed</p>
+
+    <details id="case-0" class="test-case" data-status="failed" style="margin-bottom: 20px;" open>
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(255, 0, 0);"> (TIMED OUT)</label>
+        <p style="color: rgb(255, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      <pre style="color: rgb(255, 0, 0);">timed out after 1.5s</pre>
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that test results can be grouped by tag.
+    #[test]
+    fn group_by_tag() {
+        let mut passed = TestCaseResult::new(
+            test_case_title(),
+            test_case_description(),
+            TestOutcome::Passed,
+        );
+        passed.set_html_visual(html_visual());
+        passed.set_tags(vec!["ui".to_string()]);
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![passed]);
+        let results = ViewHtml::new()
+            .with_group_by_tag(true)
+            .process_test_results(&test_results);
+
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(50, 205, 50);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>1 passed; 0 failed</p>
+
+    <h2>ui <small>(1 passed; 0 failed)</small></h2>
+
+    <details id="case-0" class="test-case" data-status="passed" style="margin-bottom: 20px;">
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(50, 205, 50);"> (ok)</label>
+        <p style="color: rgb(0, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      <div><em>Test case html visualization here</em></div>
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that the reason that a test case failed is highlighted above its visual.
+    #[test]
+    fn failure() {
+        let mut failed = TestCaseResult::failed(
+            test_case_title(),
+            test_case_description(),
+            TestFailure::new("The first pixel was the wrong color.".to_string())
+                .with_expected_actual(&[0, 0, 255], &[255, 0, 0]),
+        );
+        failed.set_html_visual(html_visual());
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![failed]);
+        let results = ViewHtml::new().process_test_results(&test_results);
+
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(255, 0, 0);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>0 passed; 1 failed</p>
+
+    <details id="case-0" class="test-case" data-status="failed" style="margin-bottom: 20px;" open>
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(255, 0, 0);"> (FAILED)</label>
+        <p style="color: rgb(255, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      <pre style="color: rgb(255, 0, 0); border-left: 4px solid rgb(255, 0, 0); padding-left: 10px;">The first pixel was the wrong color.
+expected: [0, 0, 255]
+  actual: [255, 0, 0]</pre>
+      <div><em>Test case html visualization here</em></div>
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that a test case's checks are shown as a checklist above why they failed.
+    #[test]
+    fn checks() {
+        let mut result = TestCaseResult::new(
+            test_case_title(),
+            test_case_description(),
+            TestOutcome::Passed,
+        );
+        result.push_check(TestCheck::passed("Top left".to_string()));
+        result.push_check(TestCheck::failed(
+            "Bottom right".to_string(),
+            TestFailure::new("Bottom right".to_string()),
+        ));
+        result.set_html_visual(html_visual());
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![result]);
+        let results = ViewHtml::new().process_test_results(&test_results);
 
-    <div style="margin-bottom: 20px;">
-      <div style="font-size: 24px; font-weight: bold;">
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(255, 0, 0);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>0 passed; 1 failed</p>
+
+    <details id="case-0" class="test-case" data-status="failed" style="margin-bottom: 20px;" open>
+      <summary style="font-size: 24px; font-weight: bold;">
         Test Case Title
         <label style="color: rgb(255, 0, 0);"> (FAILED)</label>
         <p style="color: rgb(255, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      <ul style="list-style: none; padding-left: 0px;">
+        <li style="color: rgb(50, 205, 50);">&#10003; Top left</li>
+        <li style="color: rgb(255, 0, 0);">&#10007; Bottom right</li>
+      </ul>
+      <pre style="color: rgb(255, 0, 0); border-left: 4px solid rgb(255, 0, 0); padding-left: 10px;">Bottom right</pre>
+      <div><em>Test case html visualization here</em></div>
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that the test cases that changed since the baseline are listed above the results.
+    #[test]
+    fn baseline() {
+        let results = |outcome| {
+            let result = TestCaseResult::new(test_case_title(), test_case_description(), outcome);
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![result])
+        };
+        let baseline = results(TestOutcome::Failed);
+        let test_results = results(TestOutcome::Passed);
+        let results = ViewHtml::new()
+            .with_baseline(baseline)
+            .process_test_results(&test_results);
+
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(50, 205, 50);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>1 passed; 0 failed</p>
+
+    <h2>Changes since baseline <small>(1 fixed)</small></h2>
+    <ul>
+      <li style="color: rgb(50, 205, 50);">Test Case Title (fixed)</li>
+    </ul>
+
+    <details id="case-0" class="test-case" data-status="passed" style="margin-bottom: 20px;">
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(50, 205, 50);"> (ok)</label>
+        <p style="color: rgb(0, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that test cases without a visual show their reason for failing and their metadata
+    /// instead.
+    #[test]
+    fn no_visual() {
+        let mut result = TestCaseResult::failed(
+            test_case_title(),
+            test_case_description(),
+            TestFailure::new("The first pixel was the wrong color.".to_string()),
+        );
+        result.insert_metadata("backend".to_string(), "metal".to_string());
+        result.insert_metadata("adapter".to_string(), "Apple M1".to_string());
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![result]);
+        let results = ViewHtml::new().process_test_results(&test_results);
+
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(255, 0, 0);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>0 passed; 1 failed</p>
+
+    <details id="case-0" class="test-case" data-status="failed" style="margin-bottom: 20px;" open>
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(255, 0, 0);"> (FAILED)</label>
+        <p style="color: rgb(255, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      <pre style="color: rgb(255, 0, 0); border-left: 4px solid rgb(255, 0, 0); padding-left: 10px;">The first pixel was the wrong color.</pre>
+      <table style="font-size: 14px;">
+        <tr><th style="text-align: left; padding-right: 10px;">adapter</th><td>Apple M1</td></tr>
+        <tr><th style="text-align: left; padding-right: 10px;">backend</th><td>metal</td></tr>
+      </table>
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that the expected, actual and diff images are shown next to their statistics.
+    #[test]
+    fn image_diff() {
+        let mut result = TestCaseResult::new(
+            test_case_title(),
+            test_case_description(),
+            TestOutcome::Passed,
+        );
+        result.push_image_diff(ImageDiff::new(
+            "Framebuffer".to_string(),
+            ImageDiffStats {
+                did_pass: true,
+                pixel_count: 4,
+                differing_pixel_count: 1,
+                max_channel_delta: 3,
+                max_perceptual_delta: 0.01,
+            },
+            b"expected".to_vec(),
+            b"actual".to_vec(),
+            b"diff".to_vec(),
+        ));
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![result]);
+        let results = ViewHtml::new().process_test_results(&test_results);
+
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(50, 205, 50);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>1 passed; 0 failed</p>
+
+    <details id="case-0" class="test-case" data-status="passed" style="margin-bottom: 20px;">
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(50, 205, 50);"> (ok)</label>
+        <p style="color: rgb(0, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      <ul style="list-style: none; padding-left: 0px;">
+        <li style="color: rgb(50, 205, 50);">&#10003; Framebuffer</li>
+      </ul>
+      <div style="display: flex; gap: 10px;">
+        <figure style="margin: 0px;"><img src="data:image/png;base64,ZXhwZWN0ZWQ=" style="image-rendering: pixelated;" /><figcaption>Expected</figcaption></figure>
+        <figure style="margin: 0px;"><img src="data:image/png;base64,YWN0dWFs" style="image-rendering: pixelated;" /><figcaption>Actual</figcaption></figure>
+        <figure style="margin: 0px;"><img src="data:image/png;base64,ZGlmZg==" style="image-rendering: pixelated;" /><figcaption>Difference</figcaption></figure>
       </div>
+      <p style="color: rgb(50, 205, 50); font-size: 14px;">Framebuffer: 1 of 4 pixels differ; max channel delta 3; max perceptual delta 0.010</p>
+      
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that image attachments are shown inline, text attachments in a `<pre>` and other
+    /// attachments as a link, with each attachment embedded only once.
+    #[test]
+    fn attachments() {
+        let mut result = TestCaseResult::new(
+            test_case_title(),
+            test_case_description(),
+            TestOutcome::Failed,
+        );
+        result.push_attachment(Attachment::new(
+            "frame.png".to_string(),
+            "image/png".to_string(),
+            b"png".to_vec(),
+        ));
+        result.push_attachment(Attachment::text(
+            "gpu.log".to_string(),
+            "<error> out of memory".to_string(),
+        ));
+        result.push_attachment(Attachment::new(
+            "pixels.rgba".to_string(),
+            "application/octet-stream".to_string(),
+            vec![255, 0, 0, 255],
+        ));
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![result]);
+        let results = ViewHtml::new().process_test_results(&test_results);
+
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(255, 0, 0);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>0 passed; 1 failed</p>
+
+    <details id="case-0" class="test-case" data-status="failed" style="margin-bottom: 20px;" open>
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(255, 0, 0);"> (FAILED)</label>
+        <p style="color: rgb(255, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      <figure style="margin: 0px 0px 10px 0px;"><img src="data:image/png;base64,cG5n" /><figcaption style="font-size: 14px;">frame.png (3 bytes)</figcaption></figure>
+      <figure style="margin: 0px 0px 10px 0px;"><pre style="border-left: 4px solid rgb(128, 128, 128); padding-left: 10px;">&lt;error&gt; out of memory</pre><figcaption style="font-size: 14px;">gpu.log (21 bytes)</figcaption></figure>
+      <figure style="margin: 0px 0px 10px 0px;"><figcaption style="font-size: 14px;"><a href="data:application/octet-stream;base64,/wAA/w==" download="pixels.rgba">pixels.rgba</a> (4 bytes)</figcaption></figure>
+      
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that test case and suite durations are shown when they were measured.
+    #[test]
+    fn durations() {
+        let mut result = TestCaseResult::new(
+            test_case_title(),
+            test_case_description(),
+            TestOutcome::Passed,
+        );
+        result.set_html_visual(html_visual());
+        result.set_duration(TestCaseDuration::new(
+            Duration::from_millis(2),
+            Duration::from_millis(10),
+        ));
+        let mut test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![result]);
+        test_results.set_duration(Duration::from_millis(1500));
+        let results = ViewHtml::new().process_test_results(&test_results);
+
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(50, 205, 50);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>1 passed; 0 failed; finished in 1.50s</p>
+
+    <details id="case-0" class="test-case" data-status="passed" style="margin-bottom: 20px;">
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(50, 205, 50);"> (ok)</label>
+        <small style="color: rgb(128, 128, 128);">12ms (2ms setup)</small>
+        <p style="color: rgb(0, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
       <div><em>Test case html visualization here</em></div>
+    </details>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that nested groups get their own headings with rolled up counts.
+    #[test]
+    fn nested_groups() {
+        let mut passed = TestCaseResult::new(
+            test_case_title(),
+            test_case_description(),
+            TestOutcome::Passed,
+        );
+        passed.set_html_visual(html_visual());
+        let section = TestGroupResults::new(
+            "Section 1.1".to_string(),
+            "".to_string(),
+            vec![passed],
+            vec![],
+        );
+        let chapter = TestGroupResults::new(
+            "Chapter 1".to_string(),
+            "The first chapter.".to_string(),
+            vec![],
+            vec![section],
+        );
+        let test_results = TestCaseResults::with_groups(
+            test_suite_title(),
+            test_suite_description(),
+            vec![],
+            vec![chapter],
+        );
+        let results = ViewHtml::new().process_test_results(&test_results);
+
+        let expected = page(
+            "My Test Suite Title",
+            r##"
+      <a href="#case-0" style="color: rgb(50, 205, 50);">Test Case Title</a>"##,
+            r##"
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <p>1 passed; 0 failed</p>
+
+    <div style="margin-left: 20px;">
+    <h2>Chapter 1 <small>(1 passed; 0 failed)</small></h2>
+    <p>The first chapter.</p>
+
+    <div style="margin-left: 20px;">
+    <h3>Section 1.1 <small>(1 passed; 0 failed)</small></h3>
+
+    <details id="case-0" class="test-case" data-status="passed" style="margin-bottom: 20px;">
+      <summary style="font-size: 24px; font-weight: bold;">
+        Test Case Title
+        <label style="color: rgb(50, 205, 50);"> (ok)</label>
+        <p style="color: rgb(0, 0, 0); font-size: 14px; margin: 0px;">Test Case Description</p>
+      </summary>
+      <div><em>Test case html visualization here</em></div>
+    </details>
     </div>
-  </body>
-</html>"#;
+    </div>"##,
+        );
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that titles, descriptions and failures are escaped, while the visual is not.
+    #[test]
+    fn escapes_text() {
+        let mut result = TestCaseResult::new(
+            "Render <canvas> quads".to_string(),
+            "Quads & \"disks\" aren't mixed.".to_string(),
+            TestOutcome::Failed,
+        );
+        result.push_failure(TestFailure::new("Expected <b>bold</b>.".to_string()));
+        result.set_html_visual(html_visual());
+        let test_results = TestCaseResults::new(
+            "Renderer <Tests>".to_string(),
+            "Tests & more.".to_string(),
+            vec![result],
+        );
+        let results = ViewHtml::new().process_test_results(&test_results);
+
+        let expected = page(
+            "Renderer &lt;Tests&gt;",
+            r##"
+      <a href="#case-0" style="color: rgb(255, 0, 0);">Render &lt;canvas&gt; quads</a>"##,
+            r##"
+    <h1>Renderer &lt;Tests&gt;</h1>
+    <p>Tests &amp; more.</p>
+    <p>0 passed; 1 failed</p>
+
+    <details id="case-0" class="test-case" data-status="failed" style="margin-bottom: 20px;" open>
+      <summary style="font-size: 24px; font-weight: bold;">
+        Render &lt;canvas&gt; quads
+        <label style="color: rgb(255, 0, 0);"> (FAILED)</label>
+        <p style="color: rgb(255, 0, 0); font-size: 14px; margin: 0px;">Quads &amp; &quot;disks&quot; aren't mixed.</p>
+      </summary>
+      <pre style="color: rgb(255, 0, 0); border-left: 4px solid rgb(255, 0, 0); padding-left: 10px;">Expected &lt;b&gt;bold&lt;/b&gt;.</pre>
+      <div><em>Test case html visualization here</em></div>
+    </details>"##,
+        );
 
         assert_eq!(results, expected)
     }
 
+    /// Verify that the legacy `html-visual` metadata key is still shown as the visual when the
+    /// test case did not set one.
+    #[test]
+    fn legacy_html_visual_metadata() {
+        let mut legacy = TestCaseResult::new(
+            "Render quads".to_string(),
+            "".to_string(),
+            TestOutcome::Passed,
+        );
+        legacy.insert_metadata(
+            "html-visual".to_string(),
+            "<em>Legacy visual</em>".to_string(),
+        );
+        let mut both = TestCaseResult::new(
+            "Render disks".to_string(),
+            "".to_string(),
+            TestOutcome::Passed,
+        );
+        both.insert_metadata(
+            "html-visual".to_string(),
+            "<em>Legacy visual</em>".to_string(),
+        );
+        both.set_html_visual(html_visual());
+        let test_results =
+            TestCaseResults::new(test_suite_title(), "".to_string(), vec![legacy, both]);
+
+        let results = ViewHtml::new().process_test_results(&test_results);
+
+        assert_eq!(results.matches("<em>Legacy visual</em>").count(), 1);
+        assert!(!results.contains("&lt;em&gt;Legacy visual"));
+        assert!(results.contains("<div><em>Test case html visualization here</em></div>"));
+    }
+
+    /// The page around the test results, with the table of contents and the main content.
+    fn page(title: &str, toc: &str, main: &str) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="utf-8" />
+    <title>{title}</title>
+    <style>{style}</style>
+  </head>
+  <body>
+    <nav id="toc">
+      <select id="status-filter">
+        <option value="all">All</option>
+        <option value="passed">Passed</option>
+        <option value="failed">Failed</option>
+        <option value="skipped">Skipped</option>
+        <option value="ignored">Ignored</option>
+        <option value="expected-failure">Expected failure</option>
+        <option value="unexpected-pass">Unexpected pass</option>
+      </select>
+      <input id="search" type="search" placeholder="Search test cases" />{toc}
+    </nav>
+    <main>{main}
+    </main>
+    <script>{script}</script>
+  </body>
+</html>"#,
+            title = title,
+            style = STYLE,
+            toc = toc,
+            main = main,
+            script = SCRIPT
+        )
+    }
+
     fn test_suite_one(pass_fail: PassedOrFailed) -> TestCaseResults {
         let mut result = TestCaseResult::new(
             test_case_title(),
             test_case_description(),
-            pass_fail.did_pass(),
+            pass_fail.outcome(),
         );
-        result.insert_metadata("html-visual".to_string(), html_visual());
+        result.set_html_visual(html_visual());
 
         TestCaseResults::new(test_suite_title(), test_suite_description(), vec![result])
     }
@@ -165,15 +1458,15 @@ mod tests {
         let mut result1 = TestCaseResult::new(
             test_case_title(),
             test_case_description(),
-            pass_fail_1.did_pass(),
+            pass_fail_1.outcome(),
         );
-        result1.insert_metadata("html-visual".to_string(), html_visual());
+        result1.set_html_visual(html_visual());
         let mut result2 = TestCaseResult::new(
             test_case_title(),
             test_case_description(),
-            pass_fail_2.did_pass(),
+            pass_fail_2.outcome(),
         );
-        result2.insert_metadata("html-visual".to_string(), html_visual());
+        result2.set_html_visual(html_visual());
 
         TestCaseResults::new(
             test_suite_title(),
@@ -198,8 +1491,8 @@ mod tests {
         "Test Case Description".to_string()
     }
 
-    fn html_visual() -> String {
-        "<div><em>Test case html visualization here</em></div>".to_string()
+    fn html_visual() -> TrustedHtml {
+        TrustedHtml::new("<div><em>Test case html visualization here</em></div>".to_string())
     }
 
     enum PassedOrFailed {
@@ -208,8 +1501,11 @@ mod tests {
     }
 
     impl PassedOrFailed {
-        fn did_pass(&self) -> bool {
-            matches!(self, PassedOrFailed::Passed)
+        fn outcome(&self) -> TestOutcome {
+            match self {
+                PassedOrFailed::Passed => TestOutcome::Passed,
+                PassedOrFailed::Failed => TestOutcome::Failed,
+            }
         }
     }
 }
@@ -0,0 +1,30 @@
+use crate::test_case_results::JsonReport;
+use crate::{TestCaseResults, Visualizer};
+
+/// Constructs a versioned JSON report of a test suite, for archiving and later comparing the
+/// results of different runs.
+///
+/// Reports can be loaded back using [`TestCaseResults::from_json`] and rendered by any
+/// visualizer.
+#[derive(Default)]
+pub struct ViewJson;
+
+impl ViewJson {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        ViewJson
+    }
+
+    /// Constructs a JSON report of a test suite.
+    ///
+    /// See [`crate::JSON_REPORT_VERSION`] for the version of the report's format.
+    pub fn process_test_results(&self, test_results: &TestCaseResults) -> String {
+        serde_json::to_string_pretty(&JsonReport::new(test_results)).unwrap()
+    }
+}
+
+impl Visualizer for ViewJson {
+    fn process_test_results(&self, test_results: &TestCaseResults) -> String {
+        ViewJson::process_test_results(self, test_results)
+    }
+}
@@ -0,0 +1,403 @@
+use crate::{TestCaseResult, TestCaseResults, TestGroupResults, TestOutcome, Visualizer};
+use std::time::Duration;
+
+/// Constructs a JUnit XML report of a test suite, for use with CI servers such as Jenkins and
+/// GitLab.
+///
+/// The test cases that do not belong to a group are reported in a `<testsuite>` named after the
+/// test suite, and each group is reported in its own `<testsuite>` named after the path to the
+/// group, such as `My Suite / Chapter 1 / Section 1.1`.
+///
+/// Test cases that were skipped or ignored, as well as expected failures, are reported as
+/// `<skipped>`.
+///
+/// The JUnit schema only allows `<properties>` on a `<testsuite>`, so each test case's metadata is
+/// reported in its test suite's `<properties>`, named after the test case and the metadata's name,
+/// such as `Render quads / renderer`.
+#[derive(Default)]
+pub struct ViewJUnitXml;
+
+impl ViewJUnitXml {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        ViewJUnitXml
+    }
+
+    /// Constructs a JUnit XML report of a test suite.
+    pub fn process_test_results(&self, test_results: &TestCaseResults) -> String {
+        let all_results: Vec<&TestCaseResult> = test_results.all_results().collect();
+
+        let mut output = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="{name}" tests="{tests}" failures="{failures}" errors="0" skipped="{skipped}"{time}>"#,
+            name = escape_xml(test_results.suite_title()),
+            tests = all_results.len(),
+            failures = failure_count(&all_results),
+            skipped = skipped_count(&all_results),
+            time = time_attribute(test_results.duration()),
+        );
+
+        if !test_results.results().is_empty() {
+            let results: Vec<&TestCaseResult> = test_results.results().iter().collect();
+            push_test_suite(&mut output, test_results.suite_title(), &results);
+        }
+
+        for group in test_results.groups() {
+            push_group(&mut output, test_results.suite_title(), group);
+        }
+
+        output += r#"
+</testsuites>"#;
+
+        output
+    }
+}
+
+impl Visualizer for ViewJUnitXml {
+    fn process_test_results(&self, test_results: &TestCaseResults) -> String {
+        ViewJUnitXml::process_test_results(self, test_results)
+    }
+}
+
+fn push_group(output: &mut String, parent_name: &str, group: &TestGroupResults) {
+    let name = format!("{} / {}", parent_name, group.title());
+
+    if !group.results().is_empty() {
+        let results: Vec<&TestCaseResult> = group.results().iter().collect();
+        push_test_suite(output, &name, &results);
+    }
+
+    for nested_group in group.groups() {
+        push_group(output, &name, nested_group);
+    }
+}
+
+fn push_test_suite(output: &mut String, name: &str, results: &[&TestCaseResult]) {
+    *output += &format!(
+        r#"
+  <testsuite name="{name}" tests="{tests}" failures="{failures}" errors="0" skipped="{skipped}"{time}>"#,
+        name = escape_xml(name),
+        tests = results.len(),
+        failures = failure_count(results),
+        skipped = skipped_count(results),
+        time = time_attribute(total_duration(results)),
+    );
+
+    push_properties(output, results);
+
+    for result in results {
+        push_test_case(output, name, result);
+    }
+
+    *output += r#"
+  </testsuite>"#;
+}
+
+/// The metadata of every test case in the test suite, sorted by name within each test case.
+fn push_properties(output: &mut String, results: &[&TestCaseResult]) {
+    if results.iter().all(|result| result.metadata().is_empty()) {
+        return;
+    }
+
+    *output += r#"
+    <properties>"#;
+    for result in results {
+        let mut metadata: Vec<(&String, &String)> = result.metadata().iter().collect();
+        metadata.sort();
+
+        for (name, value) in metadata {
+            *output += &format!(
+                r#"
+      <property name="{name}" value="{value}"/>"#,
+                name = escape_xml(&format!("{} / {}", result.title(), name)),
+                value = escape_xml(value)
+            );
+        }
+    }
+    *output += r#"
+    </properties>"#;
+}
+
+fn push_test_case(output: &mut String, classname: &str, result: &TestCaseResult) {
+    *output += &format!(
+        r#"
+    <testcase name="{name}" classname="{classname}"{time}>"#,
+        name = escape_xml(result.title()),
+        classname = escape_xml(classname),
+        time = time_attribute(result.duration().map(|duration| duration.total())),
+    );
+
+    match result.outcome() {
+        TestOutcome::Failed => {
+            let message = if result.all_failures().next().is_some() {
+                let messages: Vec<&str> = result.all_failures().map(|f| f.message()).collect();
+                messages.join("; ")
+            } else if let Some(panic) = result.panic() {
+                panic.message().to_string()
+            } else if let Some(timeout) = result.timeout() {
+                format!("timed out after {:?}", timeout)
+            } else {
+                "failed".to_string()
+            };
+
+            *output += &format!(
+                r#"
+      <failure message="{message}">"#,
+                message = escape_xml(&message)
+            );
+            if result.all_failures().next().is_some() {
+                let failures: Vec<String> = result.all_failures().map(|f| f.to_string()).collect();
+                *output += &escape_xml(&failures.join("\n\n"));
+            } else if let Some(panic) = result.panic() {
+                *output += &escape_xml(&panic.to_string());
+            }
+            *output += "</failure>";
+        }
+        TestOutcome::Skipped | TestOutcome::Ignored | TestOutcome::ExpectedFailure => {
+            let message = match result.outcome() {
+                TestOutcome::Skipped => "skipped",
+                TestOutcome::Ignored => "ignored",
+                _ => "expected failure",
+            };
+            *output += &format!(
+                r#"
+      <skipped message="{message}"/>"#,
+                message = message
+            );
+        }
+        TestOutcome::Passed | TestOutcome::UnexpectedPass => {}
+    };
+
+    *output += r#"
+    </testcase>"#;
+}
+
+fn failure_count(results: &[&TestCaseResult]) -> usize {
+    results
+        .iter()
+        .filter(|r| r.outcome() == TestOutcome::Failed)
+        .count()
+}
+
+fn skipped_count(results: &[&TestCaseResult]) -> usize {
+    results
+        .iter()
+        .filter(|r| {
+            matches!(
+                r.outcome(),
+                TestOutcome::Skipped | TestOutcome::Ignored | TestOutcome::ExpectedFailure
+            )
+        })
+        .count()
+}
+
+/// The total duration of the test cases, if all of them were timed.
+fn total_duration(results: &[&TestCaseResult]) -> Option<Duration> {
+    results
+        .iter()
+        .map(|r| r.duration().map(|duration| duration.total()))
+        .sum()
+}
+
+/// A `time` attribute with the duration in seconds, or nothing if the duration is not known.
+fn time_attribute(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => format!(r#" time="{:.3}""#, duration.as_secs_f64()),
+        None => "".to_string(),
+    }
+}
+
+/// Escape text so that it can be used in XML attributes and elements.
+///
+/// Control characters are not allowed in XML 1.0 documents, so they are removed.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TestCaseDuration, TestCasePanic};
+
+    /// Verify the report of one passing and one failing test case.
+    #[test]
+    fn one_pass_one_fail() {
+        let test_results = TestCaseResults::new(
+            test_suite_title(),
+            "".to_string(),
+            vec![
+                TestCaseResult::new(
+                    "Render quads".to_string(),
+                    "".to_string(),
+                    TestOutcome::Passed,
+                ),
+                TestCaseResult::new(
+                    "Render disks".to_string(),
+                    "".to_string(),
+                    TestOutcome::Failed,
+                ),
+            ],
+        );
+        let xml = ViewJUnitXml::new().process_test_results(&test_results);
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="My Test Suite" tests="2" failures="1" errors="0" skipped="0">
+  <testsuite name="My Test Suite" tests="2" failures="1" errors="0" skipped="0">
+    <testcase name="Render quads" classname="My Test Suite">
+    </testcase>
+    <testcase name="Render disks" classname="My Test Suite">
+      <failure message="failed"></failure>
+    </testcase>
+  </testsuite>
+</testsuites>"#;
+
+        assert_eq!(xml, expected)
+    }
+
+    /// Verify that panics, timeouts, skipped test cases and metadata are reported.
+    #[test]
+    fn panics_timeouts_skipped_and_metadata() {
+        let mut passed = TestCaseResult::new(
+            "Render quads".to_string(),
+            "".to_string(),
+            TestOutcome::Passed,
+        );
+        passed.insert_metadata("renderer".to_string(), "metal".to_string());
+        let test_results = TestCaseResults::new(
+            test_suite_title(),
+            "".to_string(),
+            vec![
+                passed,
+                TestCaseResult::panicked(
+                    "Render disks".to_string(),
+                    "".to_string(),
+                    TestCasePanic::new("oh no".to_string(), Some("src/lib.rs:1:1".to_string())),
+                ),
+                TestCaseResult::timed_out(
+                    "Render text".to_string(),
+                    "".to_string(),
+                    Duration::from_secs(2),
+                ),
+                TestCaseResult::new(
+                    "Render particles".to_string(),
+                    "".to_string(),
+                    TestOutcome::ExpectedFailure,
+                ),
+            ],
+        );
+        let xml = ViewJUnitXml::new().process_test_results(&test_results);
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="My Test Suite" tests="4" failures="2" errors="0" skipped="1">
+  <testsuite name="My Test Suite" tests="4" failures="2" errors="0" skipped="1">
+    <properties>
+      <property name="Render quads / renderer" value="metal"/>
+    </properties>
+    <testcase name="Render quads" classname="My Test Suite">
+    </testcase>
+    <testcase name="Render disks" classname="My Test Suite">
+      <failure message="oh no">panicked at &apos;oh no&apos;, src/lib.rs:1:1</failure>
+    </testcase>
+    <testcase name="Render text" classname="My Test Suite">
+      <failure message="timed out after 2s"></failure>
+    </testcase>
+    <testcase name="Render particles" classname="My Test Suite">
+      <skipped message="expected failure"/>
+    </testcase>
+  </testsuite>
+</testsuites>"#;
+
+        assert_eq!(xml, expected)
+    }
+
+    /// Verify that durations are reported in seconds when they were measured.
+    #[test]
+    fn durations() {
+        let mut result = TestCaseResult::new(
+            "Render quads".to_string(),
+            "".to_string(),
+            TestOutcome::Passed,
+        );
+        result.set_duration(TestCaseDuration::new(
+            Duration::from_millis(2),
+            Duration::from_millis(10),
+        ));
+        let mut test_results =
+            TestCaseResults::new(test_suite_title(), "".to_string(), vec![result]);
+        test_results.set_duration(Duration::from_millis(1500));
+        let xml = ViewJUnitXml::new().process_test_results(&test_results);
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="My Test Suite" tests="1" failures="0" errors="0" skipped="0" time="1.500">
+  <testsuite name="My Test Suite" tests="1" failures="0" errors="0" skipped="0" time="0.012">
+    <testcase name="Render quads" classname="My Test Suite" time="0.012">
+    </testcase>
+  </testsuite>
+</testsuites>"#;
+
+        assert_eq!(xml, expected)
+    }
+
+    /// Verify that each group is reported as its own test suite.
+    #[test]
+    fn groups() {
+        let section = TestGroupResults::new(
+            "Section 1.1".to_string(),
+            "".to_string(),
+            vec![TestCaseResult::new(
+                "Render quads".to_string(),
+                "".to_string(),
+                TestOutcome::Skipped,
+            )],
+            vec![],
+        );
+        let chapter = TestGroupResults::new(
+            "Chapter 1".to_string(),
+            "".to_string(),
+            vec![],
+            vec![section],
+        );
+        let test_results =
+            TestCaseResults::with_groups(test_suite_title(), "".to_string(), vec![], vec![chapter]);
+        let xml = ViewJUnitXml::new().process_test_results(&test_results);
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="My Test Suite" tests="1" failures="0" errors="0" skipped="1">
+  <testsuite name="My Test Suite / Chapter 1 / Section 1.1" tests="1" failures="0" errors="0" skipped="1">
+    <testcase name="Render quads" classname="My Test Suite / Chapter 1 / Section 1.1">
+      <skipped message="skipped"/>
+    </testcase>
+  </testsuite>
+</testsuites>"#;
+
+        assert_eq!(xml, expected)
+    }
+
+    /// Verify that special characters are escaped and control characters are removed.
+    #[test]
+    fn escape() {
+        assert_eq!(
+            escape_xml("<a href=\"b\">'c' & d</a>\u{1b}\n"),
+            "&lt;a href=&quot;b&quot;&gt;&apos;c&apos; &amp; d&lt;/a&gt;\n"
+        );
+    }
+
+    fn test_suite_title() -> String {
+        "My Test Suite".to_string()
+    }
+}
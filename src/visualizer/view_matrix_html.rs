@@ -0,0 +1,142 @@
+use crate::visualizer::view_html::{outcome_color, GRAY};
+use crate::visualizer::{outcome_counts, result_label};
+use crate::{escape_html, TestMatrixResults};
+
+/// Constructs an HTML table of a [`TestMatrixResults`], with a row for each test case and a
+/// column for each implementation.
+#[derive(Default)]
+pub struct ViewMatrixHtml;
+
+impl ViewMatrixHtml {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        ViewMatrixHtml
+    }
+
+    /// Constructs an HTML table of the matrix. Each implementation's heading includes its
+    /// outcome counts, and each cell is colored by the test case's outcome.
+    pub fn process_matrix_results(&self, matrix_results: &TestMatrixResults) -> String {
+        let mut output = format!(
+            r#"<html>
+  <body>
+    <h1>{title}</h1>
+    <p>{description}</p>
+    <table style="border-collapse: collapse;">
+      <tr>
+        <th style="text-align: left; padding: 4px 12px;">Test Case</th>"#,
+            title = escape_html(matrix_results.suite_title()),
+            description = escape_html(matrix_results.suite_description())
+        );
+
+        for implementation in matrix_results.implementations() {
+            let results = implementation.results();
+
+            output += &format!(
+                r#"
+        <th style="text-align: left; padding: 4px 12px;">{name} <small>({outcome_counts})</small></th>"#,
+                name = escape_html(implementation.name()),
+                outcome_counts =
+                    outcome_counts(results.all_results(), results.filtered_out_count())
+            );
+        }
+        output += r#"
+      </tr>"#;
+
+        for row in matrix_results.rows() {
+            output += &format!(
+                r#"
+      <tr>
+        <td style="padding: 4px 12px;">{title}</td>"#,
+                title = escape_html(&row.path())
+            );
+
+            for result in row.results() {
+                let (label, color) = match result {
+                    Some(result) => (result_label(result), outcome_color(result.outcome())),
+                    None => ("-", GRAY),
+                };
+
+                output += &format!(
+                    r#"
+        <td style="padding: 4px 12px; color: {color};">{label}</td>"#,
+                    color = color,
+                    label = label
+                );
+            }
+
+            output += r#"
+      </tr>"#;
+        }
+
+        output += r#"
+    </table>
+  </body>
+</html>"#;
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImplementationResults, TestCaseResult, TestCaseResults, TestOutcome};
+
+    /// Verify that test cases are shown as rows and implementations as columns.
+    #[test]
+    fn matrix() {
+        let result =
+            |title: &str, outcome| TestCaseResult::new(title.to_string(), "".to_string(), outcome);
+        let suite = |results| {
+            TestCaseResults::new(
+                "My Test Suite Title".to_string(),
+                "My Test Suite description.".to_string(),
+                results,
+            )
+        };
+        let matrix_results = TestMatrixResults::new(
+            "My Test Suite Title".to_string(),
+            "My Test Suite description.".to_string(),
+            vec![
+                ImplementationResults::new(
+                    "Metal".to_string(),
+                    suite(vec![result("Render quads", TestOutcome::Passed)]),
+                ),
+                ImplementationResults::new(
+                    "CPU".to_string(),
+                    suite(vec![
+                        result("Render quads", TestOutcome::Failed),
+                        result("Render disks", TestOutcome::Passed),
+                    ]),
+                ),
+            ],
+        );
+        let results = ViewMatrixHtml::new().process_matrix_results(&matrix_results);
+
+        let expected = r#"<html>
+  <body>
+    <h1>My Test Suite Title</h1>
+    <p>My Test Suite description.</p>
+    <table style="border-collapse: collapse;">
+      <tr>
+        <th style="text-align: left; padding: 4px 12px;">Test Case</th>
+        <th style="text-align: left; padding: 4px 12px;">Metal <small>(1 passed; 0 failed)</small></th>
+        <th style="text-align: left; padding: 4px 12px;">CPU <small>(1 passed; 1 failed)</small></th>
+      </tr>
+      <tr>
+        <td style="padding: 4px 12px;">Render quads</td>
+        <td style="padding: 4px 12px; color: rgb(50, 205, 50);">ok</td>
+        <td style="padding: 4px 12px; color: rgb(255, 0, 0);">FAILED</td>
+      </tr>
+      <tr>
+        <td style="padding: 4px 12px;">Render disks</td>
+        <td style="padding: 4px 12px; color: rgb(128, 128, 128);">-</td>
+        <td style="padding: 4px 12px; color: rgb(50, 205, 50);">ok</td>
+      </tr>
+    </table>
+  </body>
+</html>"#;
+
+        assert_eq!(results, expected)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::visualizer::{format_duration, outcome_counts, result_label};
+use crate::TestMatrixResults;
+
+/// Shown in place of a result when a test case was not run against an implementation.
+const NOT_RUN: &str = "-";
+
+/// Constructs a plain text table of a [`TestMatrixResults`], with a row for each test case and a
+/// column for each implementation.
+#[derive(Default)]
+pub struct ViewMatrixText;
+
+impl ViewMatrixText {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        ViewMatrixText
+    }
+
+    /// Constructs a plain text table of the matrix, followed by each implementation's outcome
+    /// counts.
+    pub fn process_matrix_results(&self, matrix_results: &TestMatrixResults) -> String {
+        let mut table = vec![];
+
+        let mut header = vec!["test case"];
+        header.extend(
+            matrix_results
+                .implementations()
+                .iter()
+                .map(|implementation| implementation.name()),
+        );
+        table.push(header);
+
+        let rows = matrix_results.rows();
+        let paths: Vec<String> = rows.iter().map(|row| row.path()).collect();
+        for (row, path) in rows.iter().zip(paths.iter()) {
+            let mut cells = vec![path.as_str()];
+            cells.extend(row.results().iter().map(|result| match result {
+                Some(result) => result_label(result),
+                None => NOT_RUN,
+            }));
+            table.push(cells);
+        }
+
+        let mut column_widths = vec![0; table[0].len()];
+        for cells in table.iter() {
+            for (width, cell) in column_widths.iter_mut().zip(cells.iter()) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut output = format!(
+            r#"{title}
+{description}
+"#,
+            title = matrix_results.suite_title(),
+            description = matrix_results.suite_description()
+        );
+
+        output += "\n";
+        for cells in table {
+            let line: Vec<String> = cells
+                .iter()
+                .zip(column_widths.iter())
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect();
+            output += line.join("  ").trim_end();
+            output += "\n";
+        }
+
+        output += "\n";
+        for implementation in matrix_results.implementations() {
+            let results = implementation.results();
+
+            output += &format!(
+                "{name}: {outcome_counts}{duration}\n",
+                name = implementation.name(),
+                outcome_counts =
+                    outcome_counts(results.all_results(), results.filtered_out_count()),
+                duration = match results.duration() {
+                    Some(duration) => format!("; finished in {}", format_duration(duration)),
+                    None => "".to_string(),
+                }
+            );
+        }
+
+        let passed_count = matrix_results
+            .implementations()
+            .iter()
+            .filter(|implementation| implementation.results().did_pass())
+            .count();
+        output += &format!(
+            "\ntest result: {ok_or_failed}. {passed} of {total} implementations passed",
+            ok_or_failed = if matrix_results.did_pass() {
+                "ok"
+            } else {
+                "FAILED"
+            },
+            passed = passed_count,
+            total = matrix_results.implementations().len()
+        );
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImplementationResults, TestCaseResult, TestCaseResults, TestOutcome};
+
+    /// Verify that test cases are shown as rows and implementations as columns.
+    #[test]
+    fn matrix() {
+        let matrix_results = test_matrix_results();
+        let results = ViewMatrixText::new().process_matrix_results(&matrix_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+test case     Metal  CPU
+Render quads  ok     FAILED
+Render disks  -      ok
+
+Metal: 1 passed; 0 failed; 1 filtered out
+CPU: 1 passed; 1 failed
+
+test result: FAILED. 1 of 2 implementations passed"#;
+
+        assert_eq!(results, expected)
+    }
+
+    fn test_matrix_results() -> TestMatrixResults {
+        let result =
+            |title: &str, outcome| TestCaseResult::new(title.to_string(), "".to_string(), outcome);
+        let suite = |results| {
+            TestCaseResults::new(
+                "My Test Suite Title".to_string(),
+                "My Test Suite description.".to_string(),
+                results,
+            )
+        };
+
+        let mut metal = suite(vec![result("Render quads", TestOutcome::Passed)]);
+        metal.set_filtered_out_count(1);
+        let cpu = suite(vec![
+            result("Render quads", TestOutcome::Failed),
+            result("Render disks", TestOutcome::Passed),
+        ]);
+
+        TestMatrixResults::new(
+            "My Test Suite Title".to_string(),
+            "My Test Suite description.".to_string(),
+            vec![
+                ImplementationResults::new("Metal".to_string(), metal),
+                ImplementationResults::new("CPU".to_string(), cpu),
+            ],
+        )
+    }
+}
@@ -1,17 +1,74 @@
-use crate::TestCaseResults;
+use crate::visualizer::{
+    baseline_change_counts, format_duration, group_by_tag, outcome_counts, result_label,
+};
+use crate::{
+    BaselineComparison, TestCase, TestCaseResult, TestCaseResults, TestGroup, TestGroupResults,
+    TestSuite, TestSuiteInfo, Visualizer,
+};
+use std::io::Write;
+
+/// How far each level of nested groups is indented.
+const INDENT: &str = "  ";
 
 /// A basic list of passing and failing tests, useful for visualizing in a terminal.
-pub struct ViewSimpleText {}
+#[derive(Default)]
+pub struct ViewSimpleText {
+    group_by_tag: bool,
+    stream: Option<Box<dyn Write>>,
+    baseline: Option<TestCaseResults>,
+}
 
 impl ViewSimpleText {
     #[allow(missing_docs)]
     pub fn new() -> Self {
-        ViewSimpleText {}
+        ViewSimpleText::default()
+    }
+
+    /// Group the test results by their tags, with pass and fail counts for each tag.
+    ///
+    /// A test case with multiple tags is listed under each of its tags, and test cases without
+    /// any tags are listed last under "untagged".
+    pub fn with_group_by_tag(mut self, group_by_tag: bool) -> Self {
+        self.group_by_tag = group_by_tag;
+        self
+    }
+
+    /// Write each test result to the writer as soon as its test case finishes, similar to
+    /// `cargo test`, when attached to a [`crate::TestRunner`] using
+    /// [`crate::TestRunner::with_visualizer`].
+    ///
+    /// Results are written in the order that they finish, without grouping, followed by the
+    /// suite's outcome counts once every test case has finished.
+    ///
+    /// ```
+    /// # use conformer::{TestRunner, ViewSimpleText};
+    /// let runner =
+    ///     TestRunner::new().with_visualizer(ViewSimpleText::new().with_streaming(std::io::stdout()));
+    /// ```
+    pub fn with_streaming(mut self, writer: impl Write + 'static) -> Self {
+        self.stream = Some(Box::new(writer));
+        self
+    }
+
+    /// Compare the test results to the results of a previous run, such as the last green build,
+    /// and list the test cases that regressed, were fixed, are new or were removed since then.
+    ///
+    /// See [`BaselineComparison`].
+    pub fn with_baseline(mut self, baseline: TestCaseResults) -> Self {
+        self.baseline = Some(baseline);
+        self
     }
 
     /// Prints the titles an descriptions of tests that passed and failed.
+    ///
+    /// Test cases in groups are listed under their group's title and pass and fail counts,
+    /// indented by how deeply the group is nested.
+    ///
+    /// The reasons that test cases failed, see [`crate::TestFailure`], are printed under a
+    /// "failures:" section after all of the test cases.
     pub fn process_test_results(&self, test_results: &TestCaseResults) -> String {
-        let result_or_results = if test_results.results().len() == 1 {
+        let result_count = test_results.all_results().count();
+        let result_or_results = if result_count == 1 {
             "result"
         } else {
             "results"
@@ -25,48 +82,310 @@ impl ViewSimpleText {
 "#,
             title = test_results.suite_title(),
             description = test_results.suite_description(),
-            result_count = test_results.results().len(),
+            result_count = result_count,
             result_or_results = result_or_results
         );
 
-        let mut pass_count = 0;
-        let mut fail_count = 0;
+        if self.group_by_tag {
+            for (tag, results) in group_by_tag(test_results) {
+                output += &format!(
+                    r#"
+{tag}: {outcome_counts}
+"#,
+                    tag = tag,
+                    outcome_counts = outcome_counts(results.iter().copied(), 0)
+                );
 
-        for test_result in test_results.results() {
-            let ok_or_failed = if test_result.did_pass() {
-                pass_count += 1;
-                "ok"
-            } else {
-                fail_count += 1;
-                "FAILED"
-            };
+                for test_result in results {
+                    push_test_result(&mut output, test_result, "");
+                }
+            }
+        } else {
+            for test_result in test_results.results() {
+                push_test_result(&mut output, test_result, "");
+            }
 
-            output += &format!(
-                r#"{title} ... {ok_or_failed}
-"#,
-                title = test_result.title(),
-                ok_or_failed = ok_or_failed
-            );
+            for group in test_results.groups() {
+                push_group_results(&mut output, group, "");
+            }
         }
 
-        let pass_or_fail = if fail_count == 0 { "ok" } else { "FAILED" };
+        push_failures(&mut output, test_results);
+        self.push_baseline_changes(&mut output, test_results);
+
+        output += "\n";
+        output += &test_result_summary(test_results);
+
+        output
+    }
+
+    /// Lists the titles of the test cases in the suite without running them, similar to
+    /// `cargo test -- --list`.
+    pub fn list_test_cases<TypeToTest, Case>(&self, suite: &TestSuite<TypeToTest, Case>) -> String
+    where
+        Case: ?Sized + TestCase<TypeToTest>,
+    {
+        let mut output = format!(
+            r#"{title}
+{description}
+
+"#,
+            title = suite.title,
+            description = suite.description,
+        );
+
+        push_test_cases(&mut output, &suite.test_cases, &suite.groups, "");
 
+        let test_case_count = suite.test_case_count();
+        let case_or_cases = if test_case_count == 1 {
+            "case"
+        } else {
+            "cases"
+        };
         output += &format!(
-            r#"
-test result: {pass_or_fail}. {pass_count} passed; {fail_count} failed"#,
-            pass_or_fail = pass_or_fail,
-            pass_count = pass_count,
-            fail_count = fail_count
+            "\n{count} test {case_or_cases}",
+            count = test_case_count,
+            case_or_cases = case_or_cases
         );
 
+        if suite.filtered_out_count() > 0 {
+            output += &format!("; {} filtered out", suite.filtered_out_count());
+        }
+
         output
     }
+
+    /// The test cases that changed since the baseline, if there is a baseline.
+    fn push_baseline_changes(&self, output: &mut String, test_results: &TestCaseResults) {
+        let baseline = match &self.baseline {
+            Some(baseline) => baseline,
+            None => return,
+        };
+        let comparison = BaselineComparison::new(baseline, test_results);
+
+        *output += &format!(
+            "\nchanges since baseline: {}\n",
+            baseline_change_counts(&comparison)
+        );
+        for entry in comparison.entries() {
+            if entry.change().is_change() {
+                *output += &format!(
+                    "{indent}{title} ... {change}\n",
+                    indent = INDENT,
+                    title = entry.path(),
+                    change = entry.change().label()
+                );
+            }
+        }
+    }
+
+    /// Write to the stream, if streaming is enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output could not be written to the stream.
+    fn write_to_stream(&mut self, output: &str) {
+        if let Some(stream) = self.stream.as_mut() {
+            stream.write_all(output.as_bytes()).unwrap();
+            stream.flush().unwrap();
+        }
+    }
+}
+
+impl Visualizer for ViewSimpleText {
+    fn process_test_results(&self, test_results: &TestCaseResults) -> String {
+        ViewSimpleText::process_test_results(self, test_results)
+    }
+
+    fn suite_started(&mut self, suite: &TestSuiteInfo) {
+        let case_or_cases = if suite.test_case_count() == 1 {
+            "case"
+        } else {
+            "cases"
+        };
+
+        self.write_to_stream(&format!(
+            r#"{title}
+{description}
+
+running {count} test {case_or_cases}
+"#,
+            title = suite.title(),
+            description = suite.description(),
+            count = suite.test_case_count(),
+            case_or_cases = case_or_cases
+        ));
+    }
+
+    fn test_case_finished(&mut self, _title: &str, result: &TestCaseResult) {
+        let mut output = String::new();
+        push_test_result(&mut output, result, "");
+
+        self.write_to_stream(&output);
+    }
+
+    fn suite_finished(&mut self, test_results: &TestCaseResults) {
+        let mut output = String::new();
+        push_failures(&mut output, test_results);
+        self.push_baseline_changes(&mut output, test_results);
+        output += &format!("\n{}\n", test_result_summary(test_results));
+
+        self.write_to_stream(&output);
+    }
+}
+
+/// The reasons that test cases failed, for failed test cases that gave a reason.
+fn push_failures(output: &mut String, test_results: &TestCaseResults) {
+    let failed: Vec<&TestCaseResult> = test_results
+        .all_results()
+        .filter(|result| !result.did_pass() && result.all_failures().next().is_some())
+        .collect();
+
+    if failed.is_empty() {
+        return;
+    }
+
+    *output += "\nfailures:\n";
+    for result in failed {
+        let failures: Vec<String> = result.all_failures().map(|f| f.to_string()).collect();
+
+        *output += &format!(
+            r#"
+---- {title} ----
+{failures}
+"#,
+            title = result.title(),
+            failures = failures.join("\n\n")
+        );
+    }
+}
+
+/// Whether or not the suite passed, along with its outcome counts and how long it took.
+fn test_result_summary(test_results: &TestCaseResults) -> String {
+    let pass_or_fail = if test_results.did_pass() {
+        "ok"
+    } else {
+        "FAILED"
+    };
+
+    let mut summary = format!(
+        "test result: {pass_or_fail}. {outcome_counts}",
+        pass_or_fail = pass_or_fail,
+        outcome_counts = outcome_counts(
+            test_results.all_results(),
+            test_results.filtered_out_count()
+        )
+    );
+
+    if let Some(duration) = test_results.duration() {
+        summary += &format!("; finished in {}", format_duration(duration));
+    }
+
+    summary
+}
+
+fn push_test_result(output: &mut String, test_result: &TestCaseResult, indent: &str) {
+    let duration = match test_result.duration() {
+        Some(duration) => format!(" ({})", format_duration(duration.total())),
+        None => "".to_string(),
+    };
+
+    *output += &format!(
+        r#"{indent}{title} ... {ok_or_failed}{duration}
+"#,
+        indent = indent,
+        title = test_result.title(),
+        ok_or_failed = result_label(test_result),
+        duration = duration
+    );
+
+    for check in test_result.checks() {
+        *output += &format!(
+            r#"{indent}    {name} ... {ok_or_failed}
+"#,
+            indent = indent,
+            name = check.name(),
+            ok_or_failed = if check.did_pass() { "ok" } else { "FAILED" }
+        );
+    }
+
+    if let Some(panic) = test_result.panic() {
+        *output += &format!(
+            r#"{indent}    {panic}
+"#,
+            indent = indent,
+            panic = panic
+        );
+    }
+
+    if let Some(timeout) = test_result.timeout() {
+        *output += &format!(
+            r#"{indent}    timed out after {timeout:?}
+"#,
+            indent = indent,
+            timeout = timeout
+        );
+    }
+}
+
+fn push_group_results(output: &mut String, group: &TestGroupResults, indent: &str) {
+    *output += &format!(
+        r#"
+{indent}{title}: {outcome_counts}
+"#,
+        indent = indent,
+        title = group.title(),
+        outcome_counts = outcome_counts(group.all_results(), 0)
+    );
+
+    let nested_indent = format!("{}{}", indent, INDENT);
+
+    for test_result in group.results() {
+        push_test_result(output, test_result, &nested_indent);
+    }
+
+    for nested_group in group.groups() {
+        push_group_results(output, nested_group, &nested_indent);
+    }
+}
+
+fn push_test_cases<TypeToTest, Case>(
+    output: &mut String,
+    test_cases: &[Box<Case>],
+    groups: &[TestGroup<TypeToTest, Case>],
+    indent: &str,
+) where
+    Case: ?Sized + TestCase<TypeToTest>,
+{
+    for test_case in test_cases {
+        *output += indent;
+        *output += test_case.title();
+
+        if !test_case.tags().is_empty() {
+            *output += &format!(" [{}]", test_case.tags().join(", "));
+        }
+
+        *output += "\n";
+    }
+
+    for group in groups {
+        *output += &format!("{}{}:\n", indent, group.title);
+
+        let nested_indent = format!("{}{}", indent, INDENT);
+        push_test_cases(output, &group.test_cases, &group.groups, &nested_indent);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::TestCaseResult;
+    use crate::{
+        TestCaseDuration, TestCasePanic, TestCaseResult, TestCheck, TestFailure, TestFilter,
+        TestOutcome,
+    };
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
 
     /// Verify the output of one passing test case.
     #[test]
@@ -138,11 +457,501 @@ test result: ok. 2 passed; 0 failed"#;
         assert_eq!(results, expected)
     }
 
+    /// Verify that a test case that panicked is distinguished from a test case that failed.
+    #[test]
+    fn one_test_case_panicked() {
+        let panicked = TestCaseResult::panicked(
+            test_case_title(),
+            test_case_description(),
+            TestCasePanic::new("oh no".to_string(), Some("src/lib.rs:10:5".to_string())),
+        );
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![panicked]);
+        let results = ViewSimpleText::new().process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+1 test result
+Test Case Title ... PANICKED
+    panicked at 'oh no', src/lib.rs:10:5
+
+test result: FAILED. 0 passed; 1 failed"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that a test case that timed out is distinguished from a test case that failed.
+    #[test]
+    fn one_test_case_timed_out() {
+        let timed_out = TestCaseResult::timed_out(
+            test_case_title(),
+            test_case_description(),
+            Duration::from_millis(1500),
+        );
+        let test_results = TestCaseResults::new(
+            test_suite_title(),
+            test_suite_description(),
+            vec![timed_out],
+        );
+        let results = ViewSimpleText::new().process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+1 test result
+Test Case Title ... TIMED OUT
+    timed out after 1.5s
+
+test result: FAILED. 0 passed; 1 failed"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that every outcome gets its own label and count.
+    #[test]
+    fn every_outcome() {
+        let results = TestOutcome::ALL
+            .iter()
+            .map(|outcome| {
+                TestCaseResult::new(format!("{:?}", outcome), test_case_description(), *outcome)
+            })
+            .collect();
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), results);
+        let results = ViewSimpleText::new().process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+6 test results
+Passed ... ok
+Failed ... FAILED
+Skipped ... skipped
+Ignored ... ignored
+ExpectedFailure ... expected failure
+UnexpectedPass ... UNEXPECTED PASS
+
+test result: FAILED. 1 passed; 1 failed; 1 skipped; 1 ignored; 1 expected failure; 1 unexpected pass"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that the number of test cases that were filtered out is shown.
+    #[test]
+    fn filtered_out_test_cases() {
+        let mut test_results = test_suite_one(PassedOrFailed::Passed);
+        test_results.set_filtered_out_count(3);
+        let results = ViewSimpleText::new().process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+1 test result
+Test Case Title ... ok
+
+test result: ok. 1 passed; 0 failed; 3 filtered out"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that test results can be grouped by tag.
+    #[test]
+    fn group_by_tag() {
+        let mut quads = TestCaseResult::new(
+            "Render quads".to_string(),
+            test_case_description(),
+            TestOutcome::Passed,
+        );
+        quads.set_tags(vec!["ui".to_string(), "textures".to_string()]);
+        let mut atlas = TestCaseResult::new(
+            "Render atlas".to_string(),
+            test_case_description(),
+            TestOutcome::Failed,
+        );
+        atlas.set_tags(vec!["textures".to_string()]);
+        let skinning = TestCaseResult::new(
+            "Skinned mesh".to_string(),
+            test_case_description(),
+            TestOutcome::Passed,
+        );
+
+        let test_results = TestCaseResults::new(
+            test_suite_title(),
+            test_suite_description(),
+            vec![quads, atlas, skinning],
+        );
+        let results = ViewSimpleText::new()
+            .with_group_by_tag(true)
+            .process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+3 test results
+
+textures: 1 passed; 1 failed
+Render quads ... ok
+Render atlas ... FAILED
+
+ui: 1 passed; 0 failed
+Render quads ... ok
+
+untagged: 1 passed; 0 failed
+Skinned mesh ... ok
+
+test result: FAILED. 2 passed; 1 failed"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that test results in nested groups are indented under their group.
+    #[test]
+    fn nested_groups() {
+        let section = TestGroupResults::new(
+            "Section 1.1".to_string(),
+            "".to_string(),
+            vec![TestCaseResult::panicked(
+                "Render atlas".to_string(),
+                test_case_description(),
+                TestCasePanic::new("oh no".to_string(), None),
+            )],
+            vec![],
+        );
+        let chapter = TestGroupResults::new(
+            "Chapter 1".to_string(),
+            "".to_string(),
+            vec![TestCaseResult::new(
+                "Render quads".to_string(),
+                test_case_description(),
+                TestOutcome::Passed,
+            )],
+            vec![section],
+        );
+        let test_results = TestCaseResults::with_groups(
+            test_suite_title(),
+            test_suite_description(),
+            vec![TestCaseResult::new(
+                test_case_title(),
+                test_case_description(),
+                TestOutcome::Passed,
+            )],
+            vec![chapter],
+        );
+        let results = ViewSimpleText::new().process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+3 test results
+Test Case Title ... ok
+
+Chapter 1: 1 passed; 1 failed
+  Render quads ... ok
+
+  Section 1.1: 0 passed; 1 failed
+    Render atlas ... PANICKED
+        panicked at 'oh no'
+
+test result: FAILED. 2 passed; 1 failed"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that the reasons that test cases failed are printed in a failures section.
+    #[test]
+    fn failures() {
+        let failed = TestCaseResult::failed(
+            "Render quads".to_string(),
+            test_case_description(),
+            TestFailure::new("The first pixel was the wrong color.".to_string())
+                .with_details("Rendered using the metal backend.".to_string())
+                .with_expected_actual(&[0, 0, 255], &[255, 0, 0]),
+        );
+        let failed_without_reason = TestCaseResult::new(
+            "Render disks".to_string(),
+            test_case_description(),
+            TestOutcome::Failed,
+        );
+        let test_results = TestCaseResults::new(
+            test_suite_title(),
+            test_suite_description(),
+            vec![failed, failed_without_reason],
+        );
+        let results = ViewSimpleText::new().process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+2 test results
+Render quads ... FAILED
+Render disks ... FAILED
+
+failures:
+
+---- Render quads ----
+The first pixel was the wrong color.
+Rendered using the metal backend.
+expected: [0, 0, 255]
+  actual: [255, 0, 0]
+
+test result: FAILED. 0 passed; 2 failed"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that multi-line expected and actual values are shown as a diff.
+    #[test]
+    fn failure_diff() {
+        let failed = TestCaseResult::failed(
+            "Render text".to_string(),
+            test_case_description(),
+            TestFailure::new("The glyphs were laid out incorrectly.".to_string())
+                .with_expected_actual_text("a\nb\nc".to_string(), "a\nx\nc".to_string()),
+        );
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![failed]);
+        let results = ViewSimpleText::new().process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+1 test result
+Render text ... FAILED
+
+failures:
+
+---- Render text ----
+The glyphs were laid out incorrectly.
+diff (- expected, + actual):
+  a
+- b
++ x
+  c
+
+test result: FAILED. 0 passed; 1 failed"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that a test case's checks are indented under it.
+    #[test]
+    fn checks() {
+        let mut result = TestCaseResult::new(
+            "Render quads".to_string(),
+            test_case_description(),
+            TestOutcome::Passed,
+        );
+        result.push_check(TestCheck::passed("Top left".to_string()));
+        result.push_check(TestCheck::failed(
+            "Bottom right".to_string(),
+            TestFailure::new("Bottom right".to_string())
+                .with_expected_actual(&[0, 0, 255], &[255, 0, 0]),
+        ));
+        let test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![result]);
+        let results = ViewSimpleText::new().process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+1 test result
+Render quads ... FAILED
+    Top left ... ok
+    Bottom right ... FAILED
+
+failures:
+
+---- Render quads ----
+Bottom right
+expected: [0, 0, 255]
+  actual: [255, 0, 0]
+
+test result: FAILED. 0 passed; 1 failed"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that the test cases that changed since the baseline are listed.
+    #[test]
+    fn baseline() {
+        let results = |outcomes: &[(&str, TestOutcome)]| {
+            let results = outcomes
+                .iter()
+                .map(|(title, outcome)| {
+                    TestCaseResult::new(title.to_string(), test_case_description(), *outcome)
+                })
+                .collect();
+            TestCaseResults::new(test_suite_title(), test_suite_description(), results)
+        };
+        let baseline = results(&[
+            ("Render quads", TestOutcome::Passed),
+            ("Render disks", TestOutcome::Failed),
+            ("Render text", TestOutcome::Passed),
+        ]);
+        let test_results = results(&[
+            ("Render quads", TestOutcome::Failed),
+            ("Render disks", TestOutcome::Passed),
+            ("Render lines", TestOutcome::Passed),
+        ]);
+        let results = ViewSimpleText::new()
+            .with_baseline(baseline)
+            .process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+3 test results
+Render quads ... FAILED
+Render disks ... ok
+Render lines ... ok
+
+changes since baseline: 1 regressed; 1 fixed; 1 new; 1 removed
+  Render quads ... regressed
+  Render disks ... fixed
+  Render lines ... new
+  Render text ... removed
+
+test result: FAILED. 2 passed; 1 failed"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that test case and suite durations are shown when they were measured.
+    #[test]
+    fn durations() {
+        let mut result = TestCaseResult::new(
+            test_case_title(),
+            test_case_description(),
+            TestOutcome::Passed,
+        );
+        result.set_duration(TestCaseDuration::new(
+            Duration::from_millis(2),
+            Duration::from_millis(10),
+        ));
+        let mut test_results =
+            TestCaseResults::new(test_suite_title(), test_suite_description(), vec![result]);
+        test_results.set_duration(Duration::from_millis(1500));
+        let results = ViewSimpleText::new().process_test_results(&test_results);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+1 test result
+Test Case Title ... ok (12ms)
+
+test result: ok. 1 passed; 0 failed; finished in 1.50s"#;
+
+        assert_eq!(results, expected)
+    }
+
+    /// Verify that each test result is written as soon as its test case finishes when streaming.
+    #[test]
+    fn streaming() {
+        let stream = SharedBuffer::default();
+        let mut view = ViewSimpleText::new().with_streaming(stream.clone());
+
+        view.suite_started(&TestSuiteInfo::new(
+            test_suite_title(),
+            test_suite_description(),
+            2,
+            0,
+        ));
+        view.test_case_started(&test_case_title());
+        let passed = TestCaseResult::new(
+            test_case_title(),
+            test_case_description(),
+            TestOutcome::Passed,
+        );
+        view.test_case_finished(&test_case_title(), &passed);
+
+        assert_eq!(
+            stream.contents(),
+            r#"My Test Suite Title
+My Test Suite description.
+
+running 2 test cases
+Test Case Title ... ok
+"#
+        );
+
+        let failed = TestCaseResult::new(
+            test_case_title(),
+            test_case_description(),
+            TestOutcome::Failed,
+        );
+        view.test_case_finished(&test_case_title(), &failed);
+        view.suite_finished(&TestCaseResults::new(
+            test_suite_title(),
+            test_suite_description(),
+            vec![passed, failed],
+        ));
+
+        assert_eq!(
+            stream.contents(),
+            r#"My Test Suite Title
+My Test Suite description.
+
+running 2 test cases
+Test Case Title ... ok
+Test Case Title ... FAILED
+
+test result: FAILED. 1 passed; 1 failed
+"#
+        );
+    }
+
+    /// Verify that test cases can be listed without being run.
+    #[test]
+    fn list_test_cases() {
+        let mut suite = TestSuite::new(
+            test_suite_title(),
+            test_suite_description(),
+            vec![
+                Box::new(UnrunnableTestCase {
+                    title: "Render quads",
+                    tags: vec!["textures".to_string(), "ui".to_string()],
+                }) as Box<dyn TestCase<()>>,
+                Box::new(UnrunnableTestCase {
+                    title: "Render particles",
+                    tags: vec![],
+                }),
+                Box::new(UnrunnableTestCase {
+                    title: "Skinned mesh",
+                    tags: vec![],
+                }),
+            ],
+        );
+        suite.groups.push(TestGroup::new(
+            "Chapter 1".to_string(),
+            "".to_string(),
+            vec![Box::new(UnrunnableTestCase {
+                title: "Render disks",
+                tags: vec![],
+            }) as Box<dyn TestCase<()>>],
+        ));
+        suite.apply_filter(&TestFilter::Substring("Render".to_string()));
+
+        let list = ViewSimpleText::new().list_test_cases(&suite);
+
+        let expected = r#"My Test Suite Title
+My Test Suite description.
+
+Render quads [textures, ui]
+Render particles
+Chapter 1:
+  Render disks
+
+3 test cases; 1 filtered out"#;
+
+        assert_eq!(list, expected)
+    }
+
     fn test_suite_one(pass_fail: PassedOrFailed) -> TestCaseResults {
         let result = TestCaseResult::new(
             test_case_title(),
             test_case_description(),
-            pass_fail.did_pass(),
+            pass_fail.outcome(),
         );
 
         TestCaseResults::new(test_suite_title(), test_suite_description(), vec![result])
@@ -152,12 +961,12 @@ test result: ok. 2 passed; 0 failed"#;
         let result1 = TestCaseResult::new(
             test_case_title(),
             test_case_description(),
-            pass_fail_1.did_pass(),
+            pass_fail_1.outcome(),
         );
         let result2 = TestCaseResult::new(
             test_case_title(),
             test_case_description(),
-            pass_fail_2.did_pass(),
+            pass_fail_2.outcome(),
         );
 
         TestCaseResults::new(
@@ -189,8 +998,51 @@ test result: ok. 2 passed; 0 failed"#;
     }
 
     impl PassedOrFailed {
-        fn did_pass(&self) -> bool {
-            matches!(self, PassedOrFailed::Passed)
+        fn outcome(&self) -> TestOutcome {
+            match self {
+                PassedOrFailed::Passed => TestOutcome::Passed,
+                PassedOrFailed::Failed => TestOutcome::Failed,
+            }
+        }
+    }
+
+    struct UnrunnableTestCase {
+        title: &'static str,
+        tags: Vec<String>,
+    }
+
+    impl TestCase<()> for UnrunnableTestCase {
+        fn run(self: Box<Self>, _: ()) -> TestCaseResult {
+            unreachable!("Listing test cases should not run them")
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn title(&self) -> &str {
+            self.title
+        }
+
+        fn tags(&self) -> &[String] {
+            &self.tags
+        }
+    }
+
+    #[derive(Default, Clone)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
         }
     }
 }
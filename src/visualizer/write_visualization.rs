@@ -0,0 +1,62 @@
+use crate::{TestCaseResult, TestCaseResults, TestSuiteInfo, Visualizer};
+use std::io::Write;
+
+/// Writes a [`Visualizer`]'s visualization of a test suite to a writer, such as stdout or a file,
+/// when the suite finishes.
+///
+/// Created using [`Visualizer::write_to`].
+pub struct WriteVisualization<V, W> {
+    visualizer: V,
+    writer: W,
+}
+
+impl<V: Visualizer, W: Write> WriteVisualization<V, W> {
+    #[allow(missing_docs)]
+    pub fn new(visualizer: V, writer: W) -> Self {
+        WriteVisualization { visualizer, writer }
+    }
+
+    /// The writer that the visualization is written to.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get back the writer that the visualization was written to.
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+impl<V: Visualizer, W: Write> Visualizer for WriteVisualization<V, W> {
+    fn process_test_results(&self, test_results: &TestCaseResults) -> String {
+        self.visualizer.process_test_results(test_results)
+    }
+
+    fn suite_started(&mut self, suite: &TestSuiteInfo) {
+        self.visualizer.suite_started(suite);
+    }
+
+    fn test_case_started(&mut self, title: &str) {
+        self.visualizer.test_case_started(title);
+    }
+
+    fn test_case_finished(&mut self, title: &str, result: &TestCaseResult) {
+        self.visualizer.test_case_finished(title, result);
+    }
+
+    /// Nothing is written if the visualization is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the visualization could not be written.
+    fn suite_finished(&mut self, test_results: &TestCaseResults) {
+        self.visualizer.suite_finished(test_results);
+
+        let visualization = self.visualizer.process_test_results(test_results);
+        if visualization.is_empty() {
+            return;
+        }
+        writeln!(self.writer, "{}", visualization).unwrap();
+        self.writer.flush().unwrap();
+    }
+}